@@ -0,0 +1,354 @@
+use crate::ast::{
+    BlockStatement, CatchClause, ExpressionNode, Import, MethodBody, MethodDeclaration, Node,
+    Program, QualifiedName, StatementNode, TestDeclaration, VarStatement,
+};
+use crate::token::Token;
+
+/// Renders a program as an indented tree, one node per line with its kind,
+/// its key fields and the span of the token that starts it.
+///
+/// Blocks are not shown as nodes of their own: their statements hang from
+/// the method, program or test that owns them, and from `Then`, `Else` and
+/// `Always` lines where a node has more than one block.
+pub fn dump(program: &Program) -> String {
+    let mut dumper = Dumper::default();
+
+    for import in &program.imports {
+        dumper.import(import);
+    }
+    for stmt in &program.statements {
+        dumper.statement(stmt);
+    }
+
+    dumper.out
+}
+
+#[derive(Default)]
+struct Dumper {
+    out: String,
+    depth: usize,
+}
+
+impl Dumper {
+    fn node(&mut self, label: &str, token: &Token, children: impl FnOnce(&mut Self)) {
+        self.out.push_str(&"  ".repeat(self.depth));
+        self.out.push_str(label);
+        self.out.push_str(&format!(" @{}\n", token.span));
+
+        self.depth += 1;
+        children(self);
+        self.depth -= 1;
+    }
+
+    fn leaf(&mut self, label: &str, token: &Token) {
+        self.node(label, token, |_| {});
+    }
+
+    fn import(&mut self, import: &Import) {
+        let wildcard = if import.wildcard { ".*" } else { "" };
+        let label = format!("Import {}{}", import.name.print_string(), wildcard);
+        self.leaf(&label, &import.token);
+    }
+
+    fn statement(&mut self, stmt: &StatementNode) {
+        match stmt {
+            StatementNode::Var(var) => self.var(var),
+            StatementNode::Assign(assign) => self.node(
+                &format!("Assign {}", assign.name.value),
+                &assign.token,
+                |d| d.expression(&assign.value),
+            ),
+            StatementNode::Return(ret) => self.node("Return", &ret.token, |d| {
+                if let Some(value) = &ret.ret_value {
+                    d.expression(value);
+                }
+            }),
+            StatementNode::Expression(stmt) => match &stmt.expression {
+                Some(expression) => self.expression(expression),
+                None => self.leaf("Empty", &stmt.token),
+            },
+            StatementNode::Object(object) => {
+                let label = format!(
+                    "Object {}{}",
+                    object.name.value,
+                    hierarchy(&object.parent, &object.mixins)
+                );
+                self.node(&label, &object.token, |d| {
+                    d.members(&object.fields, &object.methods)
+                });
+            }
+            StatementNode::Class(class) => {
+                let label = format!(
+                    "Class {}{}",
+                    class.name.value,
+                    hierarchy(&class.parent, &class.mixins)
+                );
+                self.node(&label, &class.token, |d| {
+                    d.members(&class.fields, &class.methods)
+                });
+            }
+            StatementNode::Mixin(mixin) => {
+                let label = format!(
+                    "Mixin {}{}",
+                    mixin.name.value,
+                    hierarchy(&None, &mixin.mixins)
+                );
+                self.node(&label, &mixin.token, |d| {
+                    d.members(&mixin.fields, &mixin.methods)
+                });
+            }
+            StatementNode::Throw(throw) => {
+                self.node("Throw", &throw.token, |d| d.expression(&throw.exception))
+            }
+            StatementNode::Package(package) => {
+                let label = format!("Package {}", package.name.print_string());
+                self.node(&label, &package.token, |d| {
+                    for stmt in &package.statements {
+                        d.statement(stmt);
+                    }
+                });
+            }
+            StatementNode::Program(program) => {
+                let label = format!("Program {}", program.name.value);
+                self.node(&label, &program.token, |d| d.block(&program.body));
+            }
+            StatementNode::Describe(describe) => {
+                let label = format!("Describe {}", describe.name.print_string());
+                self.node(&label, &describe.token, |d| {
+                    d.members(&describe.fields, &describe.methods);
+                    for test in &describe.tests {
+                        d.test(test);
+                    }
+                });
+            }
+            StatementNode::Test(test) => self.test(test),
+        }
+    }
+
+    fn test(&mut self, test: &TestDeclaration) {
+        let only = if test.only { " only" } else { "" };
+        let label = format!("Test {}{}", test.name.print_string(), only);
+        self.node(&label, &test.token, |d| d.block(&test.body));
+    }
+
+    fn var(&mut self, var: &VarStatement) {
+        let label = match var.token.literal.as_str() {
+            "const" => format!("Const {}", var.name.value),
+            _ => format!("Var {}", var.name.value),
+        };
+
+        self.node(&label, &var.token, |d| {
+            if let Some(value) = &var.value {
+                d.expression(value);
+            }
+        });
+    }
+
+    fn members(&mut self, fields: &[VarStatement], methods: &[MethodDeclaration]) {
+        for field in fields {
+            self.var(field);
+        }
+        for method in methods {
+            self.method(method);
+        }
+    }
+
+    fn method(&mut self, method: &MethodDeclaration) {
+        let parameters: Vec<&str> = method
+            .parameters
+            .iter()
+            .map(|parameter| parameter.value.as_str())
+            .collect();
+
+        let mut label = format!("Method {}({})", method.name.value, parameters.join(", "));
+        if method.overrides {
+            label.push_str(" override");
+        }
+        if let MethodBody::Abstract = method.body {
+            label.push_str(" abstract");
+        }
+
+        self.node(&label, &method.token, |d| match &method.body {
+            MethodBody::Abstract => {}
+            MethodBody::Expression(expression) => d.expression(expression),
+            MethodBody::Block(block) => d.block(block),
+        });
+    }
+
+    fn block(&mut self, block: &BlockStatement) {
+        for stmt in &block.statements {
+            self.statement(stmt);
+        }
+    }
+
+    fn catch(&mut self, catch: &CatchClause) {
+        let mut label = format!("Catch {}", catch.parameter.value);
+        if let Some(exception_type) = &catch.exception_type {
+            label.push_str(&format!(" : {}", exception_type.print_string()));
+        }
+
+        self.node(&label, &catch.token, |d| d.block(&catch.body));
+    }
+
+    fn expression(&mut self, expression: &ExpressionNode) {
+        match expression {
+            ExpressionNode::IdentifierNode(identifier) => self.leaf(
+                &format!("Identifier {}", identifier.value),
+                &identifier.token,
+            ),
+            ExpressionNode::Integer(integer) => {
+                self.leaf(&format!("Integer {}", integer.value), &integer.token)
+            }
+            ExpressionNode::Decimal(decimal) => self.leaf(
+                &format!("Decimal {}", decimal.print_string()),
+                &decimal.token,
+            ),
+            ExpressionNode::String(string) => {
+                self.leaf(&format!("String {}", string.print_string()), &string.token)
+            }
+            ExpressionNode::Boolean(boolean) => {
+                self.leaf(&format!("Boolean {}", boolean.value), &boolean.token)
+            }
+            ExpressionNode::Null(null) => self.leaf("Null", &null.token),
+            ExpressionNode::Prefix(prefix) => {
+                self.node(&format!("Prefix {}", prefix.operator), &prefix.token, |d| {
+                    d.expression(&prefix.right)
+                })
+            }
+            ExpressionNode::Infix(infix) => {
+                self.node(&format!("Infix {}", infix.operator), &infix.token, |d| {
+                    d.expression(&infix.left);
+                    d.expression(&infix.right);
+                })
+            }
+            ExpressionNode::SelfNode(self_exp) => self.leaf("Self", &self_exp.token),
+            ExpressionNode::Super(super_call) => self.node("Super", &super_call.token, |d| {
+                for argument in &super_call.arguments {
+                    d.expression(argument);
+                }
+            }),
+            ExpressionNode::Send(send) => {
+                let label = format!("Send {}/{}", send.message.value, send.arguments.len());
+                self.node(&label, &send.message.token, |d| {
+                    d.expression(&send.receiver);
+                    for argument in &send.arguments {
+                        d.expression(argument);
+                    }
+                });
+            }
+            ExpressionNode::ObjectLiteral(object) => {
+                let label = format!("ObjectLiteral{}", hierarchy(&object.parent, &object.mixins));
+                self.node(&label, &object.token, |d| {
+                    d.members(&object.fields, &object.methods)
+                });
+            }
+            ExpressionNode::New(new) => {
+                let label = format!("New {}", new.class.print_string());
+                self.node(&label, &new.token, |d| {
+                    for argument in &new.arguments {
+                        let label = format!("Argument {}", argument.name.value);
+                        d.node(&label, &argument.name.token, |d| {
+                            d.expression(&argument.value)
+                        });
+                    }
+                });
+            }
+            ExpressionNode::List(list) => self.node("List", &list.token, |d| {
+                for element in &list.elements {
+                    d.expression(element);
+                }
+            }),
+            ExpressionNode::Set(set) => self.node("Set", &set.token, |d| {
+                for element in &set.elements {
+                    d.expression(element);
+                }
+            }),
+            ExpressionNode::Try(try_exp) => self.node("Try", &try_exp.token, |d| {
+                d.block(&try_exp.body);
+                for catch in &try_exp.catches {
+                    d.catch(catch);
+                }
+                if let Some(always) = &try_exp.always {
+                    d.node("Always", &always.token, |d| d.block(always));
+                }
+            }),
+            ExpressionNode::Range(range) => self.node("Range", &range.token, |d| {
+                d.expression(&range.start);
+                d.expression(&range.end);
+            }),
+            ExpressionNode::If(if_exp) => self.node("If", &if_exp.token, |d| {
+                d.expression(&if_exp.condition);
+                d.node("Then", &if_exp.consequence.token, |d| {
+                    d.block(&if_exp.consequence)
+                });
+                if let Some(alternative) = &if_exp.alternative {
+                    d.node("Else", &alternative.token, |d| d.block(alternative));
+                }
+            }),
+            ExpressionNode::Closure(closure) => {
+                let parameters: Vec<&str> = closure
+                    .parameters
+                    .iter()
+                    .map(|parameter| parameter.value.as_str())
+                    .collect();
+                let label = format!("Closure({})", parameters.join(", "));
+                self.node(&label, &closure.token, |d| d.block(&closure.body));
+            }
+        }
+    }
+}
+
+fn hierarchy(parent: &Option<QualifiedName>, mixins: &[QualifiedName]) -> String {
+    let mut out = String::from("");
+
+    if let Some(parent) = parent {
+        out.push_str(&format!(" inherits {}", parent.print_string()));
+    }
+
+    if !mixins.is_empty() {
+        let names: Vec<String> = mixins.iter().map(|mixin| mixin.print_string()).collect();
+        out.push_str(&format!(" mixed with {}", names.join(" and ")));
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ast::dump;
+    use crate::lexer2::Lexer;
+    use crate::parser::Parser;
+
+    fn assert_dump(input: &str, expected: &str) {
+        let mut parser = Parser::new(Lexer::new(input));
+        let program = parser.parse_program().unwrap();
+        assert!(parser.errors().is_empty(), "{:?}", parser.errors());
+
+        let dumped = dump(&program);
+        assert_eq!(dumped, expected, "got:\n{}", dumped);
+    }
+
+    #[test]
+    fn test_dump_declarations() {
+        assert_dump(
+            include_str!("testdata/pepita.wlk"),
+            include_str!("testdata/pepita.ast"),
+        );
+    }
+
+    #[test]
+    fn test_dump_expressions() {
+        assert_dump(
+            include_str!("testdata/expressions.wlk"),
+            include_str!("testdata/expressions.ast"),
+        );
+    }
+
+    #[test]
+    fn test_dump_tests() {
+        assert_dump(
+            include_str!("testdata/tests.wtest"),
+            include_str!("testdata/tests.ast"),
+        );
+    }
+}
@@ -0,0 +1,1359 @@
+mod dump;
+
+pub use dump::dump;
+
+use crate::token::Token;
+#[cfg(feature = "json")]
+use serde::{Deserialize, Serialize};
+
+pub trait Node {
+    fn token_literal(&self) -> String;
+    fn print_string(&self) -> String;
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize), serde(tag = "kind"))]
+pub enum StatementNode {
+    Var(VarStatement),
+    Assign(AssignStatement),
+    Return(ReturnStatement),
+    Expression(ExpressionStatement),
+    Object(ObjectDeclaration),
+    Class(ClassDeclaration),
+    Mixin(MixinDeclaration),
+    Throw(ThrowStatement),
+    Package(Package),
+    Program(ProgramDeclaration),
+    Describe(DescribeBlock),
+    Test(TestDeclaration),
+}
+
+impl Node for StatementNode {
+    fn token_literal(&self) -> String {
+        match self {
+            Self::Var(var_stmt) => var_stmt.token_literal(),
+            Self::Assign(assign) => assign.token_literal(),
+            Self::Return(ret_stmt) => ret_stmt.token_literal(),
+            Self::Expression(expression) => expression.token_literal(),
+            Self::Object(object) => object.token_literal(),
+            Self::Class(class) => class.token_literal(),
+            Self::Mixin(mixin) => mixin.token_literal(),
+            Self::Throw(throw) => throw.token_literal(),
+            Self::Package(package) => package.token_literal(),
+            Self::Program(program) => program.token_literal(),
+            Self::Describe(describe) => describe.token_literal(),
+            Self::Test(test) => test.token_literal(),
+        }
+    }
+
+    fn print_string(&self) -> String {
+        match self {
+            Self::Var(var_stmt) => var_stmt.print_string(),
+            Self::Assign(assign) => assign.print_string(),
+            Self::Return(ret_stmt) => ret_stmt.print_string(),
+            Self::Expression(expression) => expression.print_string(),
+            Self::Object(object) => object.print_string(),
+            Self::Class(class) => class.print_string(),
+            Self::Mixin(mixin) => mixin.print_string(),
+            Self::Throw(throw) => throw.print_string(),
+            Self::Package(package) => package.print_string(),
+            Self::Program(program) => program.print_string(),
+            Self::Describe(describe) => describe.print_string(),
+            Self::Test(test) => test.print_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize), serde(tag = "kind"))]
+pub enum ExpressionNode {
+    #[cfg_attr(feature = "json", serde(rename = "Identifier"))]
+    IdentifierNode(Identifier),
+    Integer(IntegerLiteral),
+    Decimal(DecimalLiteral),
+    String(StringLiteral),
+    Boolean(BooleanLiteral),
+    Null(NullLiteral),
+    Prefix(PrefixExpression),
+    Infix(InfixExpression),
+    #[cfg_attr(feature = "json", serde(rename = "Self"))]
+    SelfNode(SelfExpression),
+    Super(SuperCall),
+    Send(MessageSend),
+    ObjectLiteral(ObjectLiteral),
+    New(NewExpression),
+    List(ListLiteral),
+    Set(SetLiteral),
+    Try(TryExpression),
+    Range(RangeLiteral),
+    If(IfExpression),
+    Closure(ClosureLiteral),
+}
+
+impl Node for ExpressionNode {
+    fn token_literal(&self) -> String {
+        match self {
+            Self::IdentifierNode(identifier) => identifier.token_literal(),
+            Self::Integer(integer) => integer.token_literal(),
+            Self::Decimal(decimal) => decimal.token_literal(),
+            Self::String(string) => string.token_literal(),
+            Self::Boolean(boolean) => boolean.token_literal(),
+            Self::Null(null) => null.token_literal(),
+            Self::Prefix(prefix) => prefix.token_literal(),
+            Self::Infix(infix) => infix.token_literal(),
+            Self::SelfNode(self_exp) => self_exp.token_literal(),
+            Self::Super(super_call) => super_call.token_literal(),
+            Self::Send(send) => send.token_literal(),
+            Self::ObjectLiteral(object) => object.token_literal(),
+            Self::New(new) => new.token_literal(),
+            Self::List(list) => list.token_literal(),
+            Self::Set(set) => set.token_literal(),
+            Self::Try(try_exp) => try_exp.token_literal(),
+            Self::Range(range) => range.token_literal(),
+            Self::If(if_exp) => if_exp.token_literal(),
+            Self::Closure(closure) => closure.token_literal(),
+        }
+    }
+
+    fn print_string(&self) -> String {
+        match self {
+            Self::IdentifierNode(identifier) => identifier.print_string(),
+            Self::Integer(integer) => integer.print_string(),
+            Self::Decimal(decimal) => decimal.print_string(),
+            Self::String(string) => string.print_string(),
+            Self::Boolean(boolean) => boolean.print_string(),
+            Self::Null(null) => null.print_string(),
+            Self::Prefix(prefix) => prefix.print_string(),
+            Self::Infix(infix) => infix.print_string(),
+            Self::SelfNode(self_exp) => self_exp.print_string(),
+            Self::Super(super_call) => super_call.print_string(),
+            Self::Send(send) => send.print_string(),
+            Self::ObjectLiteral(object) => object.print_string(),
+            Self::New(new) => new.print_string(),
+            Self::List(list) => list.print_string(),
+            Self::Set(set) => set.print_string(),
+            Self::Try(try_exp) => try_exp.print_string(),
+            Self::Range(range) => range.print_string(),
+            Self::If(if_exp) => if_exp.print_string(),
+            Self::Closure(closure) => closure.print_string(),
+        }
+    }
+}
+
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct Program {
+    pub imports: Vec<Import>,
+    pub statements: Vec<StatementNode>,
+}
+
+impl Node for Program {
+    fn token_literal(&self) -> String {
+        if let Some(import) = self.imports.first() {
+            import.token_literal()
+        } else if let Some(statement) = self.statements.first() {
+            statement.token_literal()
+        } else {
+            String::from("")
+        }
+    }
+
+    fn print_string(&self) -> String {
+        let mut output = String::from("");
+
+        for import in self.imports.as_slice() {
+            output.push_str(import.print_string().as_str());
+        }
+
+        for stmt in self.statements.as_slice() {
+            output.push_str(stmt.print_string().as_str());
+        }
+        output
+    }
+}
+
+impl Program {
+    pub fn programs(&self) -> Vec<&ProgramDeclaration> {
+        let mut programs = vec![];
+        collect_programs(&self.statements, &mut programs);
+        programs
+    }
+
+    pub fn select_program(&self, name: Option<&str>) -> Result<&ProgramDeclaration, String> {
+        let programs = self.programs();
+
+        match name {
+            Some(name) => programs
+                .into_iter()
+                .find(|program| program.name.value == name)
+                .ok_or_else(|| format!("no program named {} found", name)),
+            None => match programs.as_slice() {
+                [] => Err(String::from("no program found")),
+                [program] => Ok(program),
+                _ => {
+                    let names: Vec<&str> = programs
+                        .iter()
+                        .map(|program| program.name.value.as_str())
+                        .collect();
+                    Err(format!(
+                        "several programs found, choose one of: {}",
+                        names.join(", ")
+                    ))
+                }
+            },
+        }
+    }
+}
+
+fn collect_programs<'a>(
+    statements: &'a [StatementNode],
+    programs: &mut Vec<&'a ProgramDeclaration>,
+) {
+    for stmt in statements {
+        match stmt {
+            StatementNode::Program(program) => programs.push(program),
+            StatementNode::Package(package) => collect_programs(&package.statements, programs),
+            _ => {}
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct VarStatement {
+    pub token: Token,
+    pub name: Identifier,
+    pub value: Option<ExpressionNode>,
+}
+
+impl Node for VarStatement {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn print_string(&self) -> String {
+        let mut out = String::from("");
+
+        out.push_str(&self.token_literal());
+        out.push(' ');
+        out.push_str(&self.name.print_string());
+
+        if let Some(value) = &self.value {
+            out.push_str(" = ");
+            out.push_str(&value.print_string());
+        };
+        out.push(';');
+
+        out
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct AssignStatement {
+    pub token: Token,
+    pub name: Identifier,
+    pub value: ExpressionNode,
+}
+
+impl Node for AssignStatement {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn print_string(&self) -> String {
+        let mut out = String::from("");
+
+        out.push_str(&self.name.print_string());
+        out.push_str(" = ");
+        out.push_str(&self.value.print_string());
+        out.push(';');
+
+        out
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct Identifier {
+    pub token: Token,
+    pub value: String,
+}
+
+impl Node for Identifier {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn print_string(&self) -> String {
+        self.value.clone()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct ReturnStatement {
+    pub token: Token,
+    pub ret_value: Option<ExpressionNode>,
+}
+
+impl Node for ReturnStatement {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+    fn print_string(&self) -> String {
+        let mut out = String::from("");
+
+        out.push_str(self.token_literal().as_str());
+        out.push(' ');
+
+        if let Some(ret_value) = &self.ret_value {
+            out.push_str(ret_value.print_string().as_str());
+        }
+
+        out.push(';');
+        out
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct ExpressionStatement {
+    pub token: Token,
+    pub expression: Option<ExpressionNode>,
+}
+
+impl Node for ExpressionStatement {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn print_string(&self) -> String {
+        if let Some(expression) = &self.expression {
+            return expression.print_string();
+        }
+        String::from("")
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct IntegerLiteral {
+    pub token: Token,
+    pub value: i64,
+}
+
+impl Node for IntegerLiteral {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn print_string(&self) -> String {
+        self.token_literal()
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct DecimalLiteral {
+    pub token: Token,
+    pub value: f64,
+}
+
+impl Node for DecimalLiteral {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn print_string(&self) -> String {
+        self.token_literal()
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct StringLiteral {
+    pub token: Token,
+    pub value: String,
+}
+
+impl Node for StringLiteral {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn print_string(&self) -> String {
+        format!("{:?}", self.value)
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct BooleanLiteral {
+    pub token: Token,
+    pub value: bool,
+}
+
+impl Node for BooleanLiteral {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn print_string(&self) -> String {
+        self.token_literal()
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct NullLiteral {
+    pub token: Token,
+}
+
+impl Node for NullLiteral {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn print_string(&self) -> String {
+        self.token_literal()
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct PrefixExpression {
+    pub token: Token,
+    pub operator: String,
+    pub right: Box<ExpressionNode>,
+}
+
+impl Node for PrefixExpression {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn print_string(&self) -> String {
+        let mut out = String::from("");
+        out.push('(');
+        out.push_str(self.operator.as_str());
+        if self.operator.chars().all(char::is_alphabetic) {
+            out.push(' ');
+        }
+        out.push_str(self.right.print_string().as_str());
+        out.push(')');
+
+        out
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct InfixExpression {
+    pub token: Token,
+    pub left: Box<ExpressionNode>,
+    pub operator: String,
+    pub right: Box<ExpressionNode>,
+}
+
+impl Node for InfixExpression {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn print_string(&self) -> String {
+        let mut out = String::from("");
+        out.push('(');
+        out.push_str(self.left.print_string().as_str());
+        out.push(' ');
+        out.push_str(self.operator.as_str());
+        out.push(' ');
+        out.push_str(self.right.print_string().as_str());
+        out.push(')');
+
+        out
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct RangeLiteral {
+    pub token: Token,
+    pub start: Box<ExpressionNode>,
+    pub end: Box<ExpressionNode>,
+}
+
+impl Node for RangeLiteral {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn print_string(&self) -> String {
+        let mut out = String::from("");
+        out.push('(');
+        out.push_str(self.start.print_string().as_str());
+        out.push_str("..");
+        out.push_str(self.end.print_string().as_str());
+        out.push(')');
+
+        out
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct SelfExpression {
+    pub token: Token,
+}
+
+impl Node for SelfExpression {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn print_string(&self) -> String {
+        self.token_literal()
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct SuperCall {
+    pub token: Token,
+    pub arguments: Vec<ExpressionNode>,
+}
+
+impl Node for SuperCall {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn print_string(&self) -> String {
+        let mut out = String::from("");
+        out.push_str(self.token_literal().as_str());
+        out.push('(');
+        out.push_str(join(&self.arguments, ", ").as_str());
+        out.push(')');
+
+        out
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct MessageSend {
+    pub token: Token,
+    pub receiver: Box<ExpressionNode>,
+    pub message: Identifier,
+    pub arguments: Vec<ExpressionNode>,
+}
+
+impl Node for MessageSend {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn print_string(&self) -> String {
+        let mut out = String::from("");
+        out.push_str(self.receiver.print_string().as_str());
+        out.push('.');
+        out.push_str(self.message.print_string().as_str());
+        out.push('(');
+        out.push_str(join(&self.arguments, ", ").as_str());
+        out.push(')');
+
+        out
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct ListLiteral {
+    pub token: Token,
+    pub elements: Vec<ExpressionNode>,
+}
+
+impl Node for ListLiteral {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn print_string(&self) -> String {
+        let mut out = String::from("");
+        out.push('[');
+        out.push_str(join(&self.elements, ", ").as_str());
+        out.push(']');
+
+        out
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct SetLiteral {
+    pub token: Token,
+    pub elements: Vec<ExpressionNode>,
+}
+
+impl Node for SetLiteral {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn print_string(&self) -> String {
+        let mut out = String::from("");
+        out.push_str("#{");
+        out.push_str(join(&self.elements, ", ").as_str());
+        out.push('}');
+
+        out
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct BlockStatement {
+    pub token: Token,
+    pub statements: Vec<StatementNode>,
+}
+
+impl Node for BlockStatement {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn print_string(&self) -> String {
+        if self.statements.is_empty() {
+            return String::from("{ }");
+        }
+
+        let mut out = String::from("");
+        out.push_str("{ ");
+        out.push_str(join(&self.statements, " ").as_str());
+        out.push_str(" }");
+
+        out
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct ObjectDeclaration {
+    pub token: Token,
+    pub name: Identifier,
+    pub parent: Option<QualifiedName>,
+    pub mixins: Vec<QualifiedName>,
+    pub fields: Vec<VarStatement>,
+    pub methods: Vec<MethodDeclaration>,
+}
+
+impl Node for ObjectDeclaration {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn print_string(&self) -> String {
+        let mut out = String::from("");
+        out.push_str(self.token_literal().as_str());
+        out.push(' ');
+        out.push_str(self.name.print_string().as_str());
+        out.push(' ');
+        out.push_str(print_hierarchy(&self.parent, &self.mixins).as_str());
+        out.push_str(print_members(&self.fields, &self.methods).as_str());
+
+        out
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct ObjectLiteral {
+    pub token: Token,
+    pub parent: Option<QualifiedName>,
+    pub mixins: Vec<QualifiedName>,
+    pub fields: Vec<VarStatement>,
+    pub methods: Vec<MethodDeclaration>,
+}
+
+impl Node for ObjectLiteral {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn print_string(&self) -> String {
+        let mut out = String::from("");
+        out.push_str(self.token_literal().as_str());
+        out.push(' ');
+        out.push_str(print_hierarchy(&self.parent, &self.mixins).as_str());
+        out.push_str(print_members(&self.fields, &self.methods).as_str());
+
+        out
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct ClassDeclaration {
+    pub token: Token,
+    pub name: Identifier,
+    pub parent: Option<QualifiedName>,
+    pub mixins: Vec<QualifiedName>,
+    pub fields: Vec<VarStatement>,
+    pub methods: Vec<MethodDeclaration>,
+}
+
+impl Node for ClassDeclaration {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn print_string(&self) -> String {
+        let mut out = String::from("");
+        out.push_str(self.token_literal().as_str());
+        out.push(' ');
+        out.push_str(self.name.print_string().as_str());
+        out.push(' ');
+        out.push_str(print_hierarchy(&self.parent, &self.mixins).as_str());
+        out.push_str(print_members(&self.fields, &self.methods).as_str());
+
+        out
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct MixinDeclaration {
+    pub token: Token,
+    pub name: Identifier,
+    pub mixins: Vec<QualifiedName>,
+    pub fields: Vec<VarStatement>,
+    pub methods: Vec<MethodDeclaration>,
+}
+
+impl Node for MixinDeclaration {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn print_string(&self) -> String {
+        let mut out = String::from("");
+        out.push_str(self.token_literal().as_str());
+        out.push(' ');
+        out.push_str(self.name.print_string().as_str());
+        out.push(' ');
+        out.push_str(print_hierarchy(&None, &self.mixins).as_str());
+        out.push_str(print_members(&self.fields, &self.methods).as_str());
+
+        out
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct NewExpression {
+    pub token: Token,
+    pub class: QualifiedName,
+    pub arguments: Vec<NamedArgument>,
+}
+
+impl Node for NewExpression {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn print_string(&self) -> String {
+        let mut out = String::from("");
+        out.push_str(self.token_literal().as_str());
+        out.push(' ');
+        out.push_str(self.class.print_string().as_str());
+        out.push('(');
+        out.push_str(join(&self.arguments, ", ").as_str());
+        out.push(')');
+
+        out
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct NamedArgument {
+    pub name: Identifier,
+    pub value: ExpressionNode,
+}
+
+impl Node for NamedArgument {
+    fn token_literal(&self) -> String {
+        self.name.token_literal()
+    }
+
+    fn print_string(&self) -> String {
+        format!(
+            "{} = {}",
+            self.name.print_string(),
+            self.value.print_string()
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "json",
+    derive(Serialize, Deserialize),
+    serde(tag = "kind", content = "value")
+)]
+pub enum MethodBody {
+    Abstract,
+    Expression(ExpressionNode),
+    Block(BlockStatement),
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct MethodDeclaration {
+    pub token: Token,
+    pub name: Identifier,
+    pub parameters: Vec<Identifier>,
+    pub body: MethodBody,
+    pub overrides: bool,
+}
+
+impl Node for MethodDeclaration {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn print_string(&self) -> String {
+        let mut out = String::from("");
+
+        if self.overrides {
+            out.push_str("override ");
+        }
+        out.push_str(self.token_literal().as_str());
+        out.push(' ');
+        out.push_str(self.name.print_string().as_str());
+        out.push('(');
+        out.push_str(join(&self.parameters, ", ").as_str());
+        out.push(')');
+
+        match &self.body {
+            MethodBody::Abstract => {}
+            MethodBody::Expression(expression) => {
+                out.push_str(" = ");
+                out.push_str(expression.print_string().as_str());
+            }
+            MethodBody::Block(block) => {
+                out.push(' ');
+                out.push_str(block.print_string().as_str());
+            }
+        }
+
+        out
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct IfExpression {
+    pub token: Token,
+    pub condition: Box<ExpressionNode>,
+    pub consequence: BlockStatement,
+    pub alternative: Option<BlockStatement>,
+}
+
+impl Node for IfExpression {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn print_string(&self) -> String {
+        let mut out = String::from("");
+        out.push_str("if (");
+        out.push_str(self.condition.print_string().as_str());
+        out.push_str(") ");
+        out.push_str(self.consequence.print_string().as_str());
+
+        if let Some(alternative) = &self.alternative {
+            out.push_str(" else ");
+            out.push_str(alternative.print_string().as_str());
+        }
+
+        out
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct ClosureLiteral {
+    pub token: Token,
+    pub parameters: Vec<Identifier>,
+    pub body: BlockStatement,
+}
+
+impl Node for ClosureLiteral {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn print_string(&self) -> String {
+        let mut out = String::from("");
+        out.push('{');
+
+        if !self.parameters.is_empty() {
+            out.push(' ');
+            out.push_str(join(&self.parameters, ", ").as_str());
+            out.push_str(" =>");
+        }
+
+        if !self.body.statements.is_empty() {
+            out.push(' ');
+            out.push_str(join(&self.body.statements, " ").as_str());
+        }
+        out.push_str(" }");
+
+        out
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct ThrowStatement {
+    pub token: Token,
+    pub exception: ExpressionNode,
+}
+
+impl Node for ThrowStatement {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn print_string(&self) -> String {
+        let mut out = String::from("");
+        out.push_str(self.token_literal().as_str());
+        out.push(' ');
+        out.push_str(self.exception.print_string().as_str());
+
+        out
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct TryExpression {
+    pub token: Token,
+    pub body: BlockStatement,
+    pub catches: Vec<CatchClause>,
+    pub always: Option<BlockStatement>,
+}
+
+impl Node for TryExpression {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn print_string(&self) -> String {
+        let mut out = String::from("");
+        out.push_str(self.token_literal().as_str());
+        out.push(' ');
+        out.push_str(self.body.print_string().as_str());
+
+        for catch in self.catches.as_slice() {
+            out.push(' ');
+            out.push_str(catch.print_string().as_str());
+        }
+
+        if let Some(always) = &self.always {
+            out.push_str(" then always ");
+            out.push_str(always.print_string().as_str());
+        }
+
+        out
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct CatchClause {
+    pub token: Token,
+    pub parameter: Identifier,
+    pub exception_type: Option<QualifiedName>,
+    pub body: BlockStatement,
+}
+
+impl Node for CatchClause {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn print_string(&self) -> String {
+        let mut out = String::from("");
+        out.push_str(self.token_literal().as_str());
+        out.push(' ');
+        out.push_str(self.parameter.print_string().as_str());
+
+        if let Some(exception_type) = &self.exception_type {
+            out.push_str(" : ");
+            out.push_str(exception_type.print_string().as_str());
+        }
+
+        out.push(' ');
+        out.push_str(self.body.print_string().as_str());
+
+        out
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct QualifiedName {
+    pub token: Token,
+    pub parts: Vec<Identifier>,
+}
+
+impl Node for QualifiedName {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn print_string(&self) -> String {
+        join(&self.parts, ".")
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct Import {
+    pub token: Token,
+    pub name: QualifiedName,
+    pub wildcard: bool,
+}
+
+impl Node for Import {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn print_string(&self) -> String {
+        let mut out = String::from("");
+        out.push_str(self.token_literal().as_str());
+        out.push(' ');
+        out.push_str(self.name.print_string().as_str());
+
+        if self.wildcard {
+            out.push_str(".*");
+        }
+        out.push(';');
+
+        out
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct Package {
+    pub token: Token,
+    pub name: QualifiedName,
+    pub statements: Vec<StatementNode>,
+}
+
+impl Node for Package {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn print_string(&self) -> String {
+        let mut out = String::from("");
+        out.push_str(self.token_literal().as_str());
+        out.push(' ');
+        out.push_str(self.name.print_string().as_str());
+
+        if self.statements.is_empty() {
+            out.push_str(" { }");
+        } else {
+            out.push_str(" { ");
+            out.push_str(join(&self.statements, " ").as_str());
+            out.push_str(" }");
+        }
+
+        out
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct ProgramDeclaration {
+    pub token: Token,
+    pub name: Identifier,
+    pub body: BlockStatement,
+}
+
+impl Node for ProgramDeclaration {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn print_string(&self) -> String {
+        let mut out = String::from("");
+        out.push_str(self.token_literal().as_str());
+        out.push(' ');
+        out.push_str(self.name.print_string().as_str());
+        out.push(' ');
+        out.push_str(self.body.print_string().as_str());
+
+        out
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct DescribeBlock {
+    pub token: Token,
+    pub name: StringLiteral,
+    pub fields: Vec<VarStatement>,
+    pub methods: Vec<MethodDeclaration>,
+    pub tests: Vec<TestDeclaration>,
+}
+
+impl DescribeBlock {
+    pub fn initialize(&self) -> Option<&MethodDeclaration> {
+        self.methods
+            .iter()
+            .find(|method| method.name.value == "initialize" && method.parameters.is_empty())
+    }
+}
+
+impl Node for DescribeBlock {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn print_string(&self) -> String {
+        let mut members: Vec<String> = self.fields.iter().map(|f| f.print_string()).collect();
+        members.extend(self.methods.iter().map(|method| method.print_string()));
+        members.extend(self.tests.iter().map(|test| test.print_string()));
+
+        let mut out = String::from("");
+        out.push_str(self.token_literal().as_str());
+        out.push(' ');
+        out.push_str(self.name.print_string().as_str());
+
+        if members.is_empty() {
+            out.push_str(" { }");
+        } else {
+            out.push_str(" { ");
+            out.push_str(members.join(" ").as_str());
+            out.push_str(" }");
+        }
+
+        out
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct TestDeclaration {
+    pub token: Token,
+    pub name: StringLiteral,
+    pub only: bool,
+    pub body: BlockStatement,
+}
+
+impl Node for TestDeclaration {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn print_string(&self) -> String {
+        let mut out = String::from("");
+
+        if self.only {
+            out.push_str("only ");
+        }
+        out.push_str(self.token_literal().as_str());
+        out.push(' ');
+        out.push_str(self.name.print_string().as_str());
+        out.push(' ');
+        out.push_str(self.body.print_string().as_str());
+
+        out
+    }
+}
+
+fn join<T: Node>(nodes: &[T], separator: &str) -> String {
+    nodes
+        .iter()
+        .map(|node| node.print_string())
+        .collect::<Vec<String>>()
+        .join(separator)
+}
+
+fn print_hierarchy(parent: &Option<QualifiedName>, mixins: &[QualifiedName]) -> String {
+    let mut out = String::from("");
+
+    if let Some(parent) = parent {
+        out.push_str(format!("inherits {} ", parent.print_string()).as_str());
+    }
+
+    if !mixins.is_empty() {
+        out.push_str(format!("mixed with {} ", join(mixins, " and ")).as_str());
+    }
+
+    out
+}
+
+fn print_members(fields: &[VarStatement], methods: &[MethodDeclaration]) -> String {
+    let mut members: Vec<String> = fields.iter().map(|field| field.print_string()).collect();
+    members.extend(methods.iter().map(|method| method.print_string()));
+
+    if members.is_empty() {
+        return String::from("{ }");
+    }
+
+    let mut out = String::from("");
+    out.push_str("{ ");
+    out.push_str(members.join(" ").as_str());
+    out.push_str(" }");
+
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ast::{ExpressionNode, Identifier, Node, Program, StatementNode, VarStatement};
+    use crate::lexer2::Lexer;
+    use crate::parser::Parser;
+    use crate::token::{Token, TokenKind};
+
+    #[test]
+    fn test_print_string() {
+        let program = Program {
+            imports: vec![],
+            statements: vec![StatementNode::Var(VarStatement {
+                token: Token {
+                    kind: TokenKind::Var,
+                    literal: String::from("var"),
+                    ..Default::default()
+                },
+                name: Identifier {
+                    token: Token {
+                        kind: TokenKind::Identifier,
+                        literal: String::from("myVar"),
+                        ..Default::default()
+                    },
+                    value: String::from("myVar"),
+                },
+                value: Some(ExpressionNode::IdentifierNode(Identifier {
+                    token: Token {
+                        kind: TokenKind::Identifier,
+                        literal: String::from("anotherVar"),
+                        ..Default::default()
+                    },
+                    value: String::from("anotherVar"),
+                })),
+            })],
+        };
+
+        assert_eq!(
+            program.print_string(),
+            String::from("var myVar = anotherVar;"),
+            "print string wrong, got = {}",
+            program.print_string()
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_json_round_trip() {
+        let inputs = [
+            include_str!("testdata/pepita.wlk"),
+            include_str!("testdata/expressions.wlk"),
+            include_str!("testdata/tests.wtest"),
+        ];
+
+        for input in inputs {
+            let mut parser = Parser::new(Lexer::new(input));
+            let program = parser.parse_program().unwrap();
+
+            let json = serde_json::to_string(&program).unwrap();
+            let parsed: Program = serde_json::from_str(&json).unwrap();
+
+            assert_eq!(parsed.print_string(), program.print_string());
+            assert_eq!(crate::ast::dump(&parsed), crate::ast::dump(&program));
+        }
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_json_tags_and_spans() {
+        let mut parser = Parser::new(Lexer::new("x.volar(self)"));
+        let program = parser.parse_program().unwrap();
+        let json = serde_json::to_value(&program).unwrap();
+
+        let send = &json["statements"][0];
+        assert_eq!(send["kind"], "Expression");
+        assert_eq!(send["expression"]["kind"], "Send");
+        assert_eq!(send["expression"]["receiver"]["kind"], "Identifier");
+        assert_eq!(send["expression"]["arguments"][0]["kind"], "Self");
+        assert_eq!(
+            send["expression"]["message"]["token"]["span"],
+            serde_json::json!({
+                "start": { "line": 1, "column": 3 },
+                "end": { "line": 1, "column": 8 }
+            })
+        );
+    }
+
+    #[test]
+    fn test_select_program() {
+        let input = r#"
+            program saludo { }
+            package ejemplos {
+                program despedida { }
+            }
+        "#;
+
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program().unwrap();
+
+        let names: Vec<&str> = program
+            .programs()
+            .iter()
+            .map(|program| program.name.value.as_str())
+            .collect();
+        assert_eq!(names, vec!["saludo", "despedida"]);
+
+        assert_eq!(
+            program
+                .select_program(Some("despedida"))
+                .unwrap()
+                .name
+                .value,
+            "despedida"
+        );
+        assert_eq!(
+            program.select_program(Some("otro")).unwrap_err(),
+            "no program named otro found"
+        );
+        assert_eq!(
+            program.select_program(None).unwrap_err(),
+            "several programs found, choose one of: saludo, despedida"
+        );
+    }
+
+    #[test]
+    fn test_select_single_program() {
+        let lexer = Lexer::new("object pepita { } program saludo { }");
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program().unwrap();
+
+        assert_eq!(program.select_program(None).unwrap().name.value, "saludo");
+
+        let lexer = Lexer::new("object pepita { }");
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program().unwrap();
+
+        assert_eq!(
+            program.select_program(None).unwrap_err(),
+            "no program found"
+        );
+    }
+}
@@ -0,0 +1,451 @@
+use crate::ast;
+use crate::ast::Program;
+use crate::evaluator::Evaluator;
+use crate::lexer2::Lexer;
+use crate::parser::Parser;
+use crate::repl;
+use crate::runner;
+use crate::runner::Format;
+use crate::token::TokenKind;
+use crate::validator::validate;
+use std::fs;
+use std::io;
+#[cfg(feature = "line-editing")]
+use std::io::IsTerminal;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Everything went fine.
+pub const EXIT_SUCCESS: u8 = 0;
+/// The program, its tests or its checks failed.
+pub const EXIT_FAILURE: u8 = 1;
+/// Rollok was called the wrong way or could not read its input.
+pub const EXIT_USAGE: u8 = 2;
+
+const USAGE: &str = "usage: rollok <command> [arguments]
+
+commands:
+    run <file> [program]    run a program, choosing it by name if the file has several
+    repl                    start an interactive session
+    test [path] [--format summary|junit|tap]
+                            run the .wtest files under path (default: .)
+    lex <file> [--json]     print the tokens of a file
+    parse <file> [--json]   print the syntax tree of a file
+    check <file>            parse and validate a file
+    help                    show this message";
+
+/// Runs the command named by the arguments (without the binary name),
+/// writing results to `out` and errors to `err`. Returns the exit code.
+pub fn run(args: &[String], out: &mut impl Write, err: &mut impl Write) -> u8 {
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    let result = match args.as_slice() {
+        ["run", file] => run_program(file, None, out, err),
+        ["run", file, program] => run_program(file, Some(program), out, err),
+        ["repl"] => {
+            writeln!(out, "Hello! This is the Rollok Programming Language!")
+                .expect("should have written the greeting");
+            Ok(start_repl(err))
+        }
+        ["test", options @ ..] => run_tests(options, out, err),
+        ["lex", file] => lex(file, false, out),
+        ["lex", file, "--json"] => lex(file, true, out),
+        ["parse", file] => parse(file, false, out, err),
+        ["parse", file, "--json"] => parse(file, true, out, err),
+        ["check", file] => check(file, out, err),
+        ["help" | "--help" | "-h"] => {
+            writeln!(out, "{}", USAGE).expect("should have written the usage");
+            Ok(EXIT_SUCCESS)
+        }
+        [] => Err(Error::Usage(String::from("missing command"))),
+        [command, ..] if is_command(command) => {
+            Err(Error::Usage(format!("wrong arguments for {}", command)))
+        }
+        [command, ..] => Err(Error::Usage(format!("unknown command {}", command))),
+    };
+
+    match result {
+        Ok(code) => code,
+        Err(Error::Usage(message)) => {
+            writeln!(err, "rollok: {}\n\n{}", message, USAGE)
+                .expect("should have written the usage");
+            EXIT_USAGE
+        }
+        Err(Error::Input(message)) => {
+            writeln!(err, "rollok: {}", message).expect("should have written the error");
+            EXIT_USAGE
+        }
+    }
+}
+
+/// Why a command did not get to run.
+enum Error {
+    /// The arguments were wrong, so the usage is shown with the message.
+    Usage(String),
+    /// The input could not be read.
+    Input(String),
+}
+
+/// Uses line editing when it is compiled in and stdin is a terminal, and
+/// plain line reading otherwise (for example when input is piped in).
+fn start_repl(err: &mut impl Write) -> u8 {
+    #[cfg(feature = "line-editing")]
+    if io::stdin().is_terminal() {
+        return match repl::editor::start() {
+            Ok(()) => EXIT_SUCCESS,
+            Err(e) => {
+                writeln!(err, "rollok: {}", e).expect("should have written the error");
+                EXIT_FAILURE
+            }
+        };
+    }
+
+    match repl::start(io::stdin().lock(), io::stdout()) {
+        Ok(()) => EXIT_SUCCESS,
+        // Whoever was reading the output has gone away, so there is no one
+        // left to tell.
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => EXIT_FAILURE,
+        Err(e) => {
+            writeln!(err, "rollok: {}", e).expect("should have written the error");
+            EXIT_FAILURE
+        }
+    }
+}
+
+fn is_command(name: &str) -> bool {
+    matches!(name, "run" | "repl" | "test" | "lex" | "parse" | "check")
+}
+
+fn read(file: &str) -> Result<String, Error> {
+    fs::read_to_string(file).map_err(|e| Error::Input(format!("could not read {}: {}", file, e)))
+}
+
+/// Parses a file, reporting syntax errors on `err`.
+fn parse_file(file: &str, input: &str, err: &mut impl Write) -> Option<Program> {
+    let mut parser = Parser::new(Lexer::new(input));
+    let program = parser.parse_program();
+
+    if parser.errors().is_empty() {
+        if let Some(program) = program {
+            return Some(program);
+        }
+    }
+
+    for error in parser.errors() {
+        writeln!(err, "{}: {}", file, error).expect("should have written the error");
+    }
+    None
+}
+
+/// Parses and validates a file, reporting every problem on `err`.
+fn load(file: &str, input: &str, err: &mut impl Write) -> Option<Program> {
+    let program = parse_file(file, input, err)?;
+
+    let errors = validate(&program);
+    for error in &errors {
+        writeln!(err, "{}: {}", file, error).expect("should have written the error");
+    }
+
+    errors.is_empty().then_some(program)
+}
+
+fn run_program(
+    file: &str,
+    name: Option<&str>,
+    out: &mut impl Write,
+    err: &mut impl Write,
+) -> Result<u8, Error> {
+    let input = read(file)?;
+    let program = match load(file, &input, err) {
+        Some(program) => program,
+        None => return Ok(EXIT_FAILURE),
+    };
+
+    let mut evaluator = Evaluator::with_output(out);
+    evaluator.set_file(file);
+
+    match evaluator.run_program(&program, name) {
+        Ok(_) => Ok(EXIT_SUCCESS),
+        Err(error) => {
+            writeln!(err, "{}", error).expect("should have written the error");
+            Ok(EXIT_FAILURE)
+        }
+    }
+}
+
+fn run_tests(options: &[&str], out: &mut impl Write, err: &mut impl Write) -> Result<u8, Error> {
+    let mut path = None;
+    let mut format = Format::Summary;
+
+    let mut options = options.iter();
+    while let Some(option) = options.next() {
+        match *option {
+            "--format" => match options.next() {
+                Some(value) => format = value.parse().map_err(Error::Usage)?,
+                None => return Err(Error::Usage(String::from("--format needs a value"))),
+            },
+            _ if option.starts_with("--format=") => {
+                format = option["--format=".len()..].parse().map_err(Error::Usage)?
+            }
+            _ if path.is_none() => path = Some(PathBuf::from(option)),
+            _ => return Err(Error::Usage(format!("unexpected argument {}", option))),
+        }
+    }
+
+    let path = path.unwrap_or_else(|| PathBuf::from("."));
+    let report = runner::run(&path)
+        .map_err(|e| Error::Input(format!("could not read {}: {}", path.display(), e)))?;
+
+    report
+        .write(format, out)
+        .expect("should have written the test report");
+
+    if report.success() {
+        Ok(EXIT_SUCCESS)
+    } else {
+        writeln!(
+            err,
+            "{} of {} tests did not pass",
+            report.results.len() - report.passed() + report.file_errors.len(),
+            report.results.len() + report.file_errors.len()
+        )
+        .expect("should have written the error");
+        Ok(EXIT_FAILURE)
+    }
+}
+
+/// Writes `value` as pretty-printed JSON followed by a newline.
+#[cfg(feature = "json")]
+fn write_json(value: &impl serde::Serialize, out: &mut impl Write) -> Result<u8, Error> {
+    serde_json::to_writer_pretty(&mut *out, value).expect("should have written the JSON");
+    writeln!(out).expect("should have written the JSON");
+    Ok(EXIT_SUCCESS)
+}
+
+#[cfg(not(feature = "json"))]
+fn write_json<T>(_: &T, _: &mut impl Write) -> Result<u8, Error> {
+    Err(Error::Usage(String::from(
+        "--json needs rollok built with the json feature",
+    )))
+}
+
+fn lex(file: &str, json: bool, out: &mut impl Write) -> Result<u8, Error> {
+    let input = read(file)?;
+    let mut lexer = Lexer::new(&input);
+
+    let mut tokens = vec![];
+    loop {
+        let token = lexer.next_token();
+        if token.kind == TokenKind::EOF {
+            break;
+        }
+        tokens.push(token);
+    }
+
+    if json {
+        return write_json(&tokens, out);
+    }
+
+    for token in &tokens {
+        writeln!(out, "{token:?}").expect("should have written the token");
+    }
+    Ok(EXIT_SUCCESS)
+}
+
+fn parse(file: &str, json: bool, out: &mut impl Write, err: &mut impl Write) -> Result<u8, Error> {
+    let input = read(file)?;
+
+    match parse_file(file, &input, err) {
+        Some(program) if json => write_json(&program, out),
+        Some(program) => {
+            write!(out, "{}", ast::dump(&program)).expect("should have written the tree");
+            Ok(EXIT_SUCCESS)
+        }
+        None => Ok(EXIT_FAILURE),
+    }
+}
+
+fn check(file: &str, out: &mut impl Write, err: &mut impl Write) -> Result<u8, Error> {
+    let input = read(file)?;
+
+    match load(file, &input, err) {
+        Some(_) => {
+            writeln!(out, "{}: ok", file).expect("should have written the result");
+            Ok(EXIT_SUCCESS)
+        }
+        None => Ok(EXIT_FAILURE),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[cfg(feature = "json")]
+    use crate::ast::{Node, Program};
+    use crate::cli::{run, EXIT_FAILURE, EXIT_SUCCESS, EXIT_USAGE};
+    #[cfg(feature = "json")]
+    use crate::token::Token;
+    use std::fs;
+    use std::path::PathBuf;
+
+    /// Runs the CLI and returns the exit code, stdout and stderr.
+    fn cli(args: &[&str]) -> (u8, String, String) {
+        let args: Vec<String> = args.iter().map(|arg| String::from(*arg)).collect();
+        let mut out = vec![];
+        let mut err = vec![];
+
+        let code = run(&args, &mut out, &mut err);
+        (
+            code,
+            String::from_utf8(out).unwrap(),
+            String::from_utf8(err).unwrap(),
+        )
+    }
+
+    fn fixture(name: &str, contents: &str) -> String {
+        let dir = std::env::temp_dir().join(format!("rollok-cli-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let path: PathBuf = dir.join(name);
+        fs::write(&path, contents).unwrap();
+        path.display().to_string()
+    }
+
+    #[test]
+    fn test_usage_errors() {
+        let (code, out, err) = cli(&[]);
+        assert_eq!((code, out.as_str()), (EXIT_USAGE, ""));
+        assert!(err.starts_with("rollok: missing command\n\nusage: rollok"));
+
+        let (code, _, err) = cli(&["compile", "a.wlk"]);
+        assert_eq!(code, EXIT_USAGE);
+        assert!(err.starts_with("rollok: unknown command compile\n"));
+
+        let (code, _, err) = cli(&["run"]);
+        assert_eq!(code, EXIT_USAGE);
+        assert!(err.starts_with("rollok: wrong arguments for run\n"));
+
+        let (code, _, err) = cli(&["check", "/nonexistent/a.wlk"]);
+        assert_eq!(code, EXIT_USAGE);
+        assert!(err.starts_with("rollok: could not read /nonexistent/a.wlk: "));
+        assert!(!err.contains("usage:"));
+
+        let (code, out, err) = cli(&["help"]);
+        assert_eq!((code, err.as_str()), (EXIT_SUCCESS, ""));
+        assert!(out.starts_with("usage: rollok <command>"));
+    }
+
+    #[test]
+    fn test_run() {
+        let file = fixture(
+            "saludo.wpgm",
+            "object calc { method doble(n) = n * 2 }\nprogram main { console.println(calc.doble(2)) }\nprogram roto { calc.triple(2) }",
+        );
+
+        assert_eq!(
+            cli(&["run", &file, "main"]),
+            (EXIT_SUCCESS, String::from("4\n"), String::new())
+        );
+
+        let (code, out, err) = cli(&["run", &file, "roto"]);
+        assert_eq!((code, out.as_str()), (EXIT_FAILURE, ""));
+        assert_eq!(
+            err,
+            format!(
+                "MessageNotUnderstoodException: calc does not understand triple/1\n    at program roto [{}:3:21]\n",
+                file
+            )
+        );
+
+        let (code, _, err) = cli(&["run", &file]);
+        assert_eq!(code, EXIT_FAILURE);
+        assert_eq!(err, "several programs found, choose one of: main, roto\n");
+    }
+
+    #[test]
+    fn test_lex_parse_and_check() {
+        let file = fixture("ave.wlk", "object pepita { var energia = 10 }");
+
+        let (code, out, _) = cli(&["lex", &file]);
+        assert_eq!(code, EXIT_SUCCESS);
+        assert_eq!(out.lines().count(), 8);
+        assert!(out.starts_with("Token { kind: Object, literal: \"object\""));
+
+        assert_eq!(
+            cli(&["parse", &file]),
+            (
+                EXIT_SUCCESS,
+                String::from(
+                    "Object pepita @1:1-1:7\n  Var energia @1:17-1:20\n    Integer 10 @1:31-1:33\n"
+                ),
+                String::new()
+            )
+        );
+
+        assert_eq!(
+            cli(&["check", &file]),
+            (EXIT_SUCCESS, format!("{}: ok\n", file), String::new())
+        );
+
+        let broken = fixture("roto.wlk", "object pepita { method volar() = self }\nself");
+        assert_eq!(
+            cli(&["check", &broken]),
+            (
+                EXIT_FAILURE,
+                String::new(),
+                format!("{}: self cannot be used at program top level\n", broken)
+            )
+        );
+
+        let unparsable = fixture("incompleto.wlk", "object pepita {");
+        let (code, out, err) = cli(&["parse", &unparsable]);
+        assert_eq!((code, out.as_str()), (EXIT_FAILURE, ""));
+        assert!(err.starts_with(&format!("{}: ", unparsable)));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_json_output() {
+        let file = fixture("json.wlk", "var x = 1");
+
+        let (code, out, _) = cli(&["lex", &file, "--json"]);
+        assert_eq!(code, EXIT_SUCCESS);
+        let tokens: Vec<Token> = serde_json::from_str(&out).unwrap();
+        assert_eq!(tokens.len(), 4);
+        assert_eq!(tokens[3].literal, "1");
+
+        let (code, out, _) = cli(&["parse", &file, "--json"]);
+        assert_eq!(code, EXIT_SUCCESS);
+        let program: Program = serde_json::from_str(&out).unwrap();
+        assert_eq!(program.print_string(), "var x = 1;");
+    }
+
+    #[cfg(not(feature = "json"))]
+    #[test]
+    fn test_json_output() {
+        let file = fixture("json.wlk", "var x = 1");
+
+        let (code, out, err) = cli(&["parse", &file, "--json"]);
+        assert_eq!((code, out.as_str()), (EXIT_USAGE, ""));
+        assert!(err.starts_with("rollok: --json needs rollok built with the json feature\n"));
+    }
+
+    #[test]
+    fn test_test_command() {
+        let file = fixture("pepita.wtest", "test \"ok\" { assert.that(true) }");
+
+        let (code, out, err) = cli(&["test", &file, "--format", "tap"]);
+        assert_eq!((code, err.as_str()), (EXIT_SUCCESS, ""));
+        assert_eq!(out, "TAP version 13\n1..1\nok 1 - ok\n");
+
+        let failing = fixture("roto.wtest", "test \"falla\" { assert.that(false) }");
+        let (code, _, err) = cli(&["test", &failing]);
+        assert_eq!(
+            (code, err.as_str()),
+            (EXIT_FAILURE, "1 of 1 tests did not pass\n")
+        );
+
+        let (code, _, err) = cli(&["test", &file, "--format=xml"]);
+        assert_eq!(code, EXIT_USAGE);
+        assert!(err.starts_with("rollok: unknown report format xml"));
+    }
+}
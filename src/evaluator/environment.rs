@@ -0,0 +1,160 @@
+use crate::runtime::value::Value;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+#[derive(Debug, Clone)]
+struct Binding {
+    value: Value,
+    constant: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct Environment {
+    store: HashMap<String, Binding>,
+    outer: Option<Rc<RefCell<Environment>>>,
+}
+
+impl Environment {
+    pub fn new() -> Self {
+        Self {
+            store: HashMap::new(),
+            outer: None,
+        }
+    }
+
+    pub fn new_enclosed(outer: Rc<RefCell<Environment>>) -> Self {
+        Self {
+            store: HashMap::new(),
+            outer: Some(outer),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<Value> {
+        match self.store.get(name) {
+            Some(binding) => Some(binding.value.clone()),
+            None => match &self.outer {
+                Some(outer) => outer.borrow().get(name),
+                None => None,
+            },
+        }
+    }
+
+    /// The bindings made in this environment (not the enclosing ones) as
+    /// (name, value, constant), sorted by name.
+    pub fn bindings(&self) -> Vec<(String, Value, bool)> {
+        let mut bindings: Vec<(String, Value, bool)> = self
+            .store
+            .iter()
+            .map(|(name, binding)| (name.clone(), binding.value.clone(), binding.constant))
+            .collect();
+
+        bindings.sort_by(|a, b| a.0.cmp(&b.0));
+        bindings
+    }
+
+    pub fn define(&mut self, name: &str, value: Value, constant: bool) -> Result<(), String> {
+        if self.store.contains_key(name) {
+            return Err(format!("{} is already defined", name));
+        }
+
+        self.store
+            .insert(String::from(name), Binding { value, constant });
+        Ok(())
+    }
+
+    pub fn assign(&mut self, name: &str, value: Value) -> Result<(), String> {
+        match self.store.get_mut(name) {
+            Some(binding) if binding.constant => Err(format!("cannot modify constant {}", name)),
+            Some(binding) => {
+                binding.value = value;
+                Ok(())
+            }
+            None => match &self.outer {
+                Some(outer) => outer.borrow_mut().assign(name, value),
+                None => Err(format!("reference {} not found", name)),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::evaluator::environment::Environment;
+    use crate::runtime::value::Value;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_nested_lookup_and_assignment() {
+        let outer = Rc::new(RefCell::new(Environment::new()));
+        outer
+            .borrow_mut()
+            .define("x", Value::Number(1.0), false)
+            .unwrap();
+        outer
+            .borrow_mut()
+            .define("limite", Value::Number(10.0), true)
+            .unwrap();
+
+        let mut inner = Environment::new_enclosed(Rc::clone(&outer));
+        inner.define("y", Value::Number(2.0), false).unwrap();
+
+        assert_eq!(inner.get("x"), Some(Value::Number(1.0)));
+        assert_eq!(inner.get("y"), Some(Value::Number(2.0)));
+        assert_eq!(outer.borrow().get("y"), None);
+
+        inner.assign("x", Value::Number(5.0)).unwrap();
+        assert_eq!(outer.borrow().get("x"), Some(Value::Number(5.0)));
+
+        assert_eq!(
+            inner.assign("limite", Value::Null),
+            Err(String::from("cannot modify constant limite"))
+        );
+        assert_eq!(
+            inner.assign("z", Value::Null),
+            Err(String::from("reference z not found"))
+        );
+        assert_eq!(
+            inner.define("y", Value::Null, false),
+            Err(String::from("y is already defined"))
+        );
+    }
+
+    #[test]
+    fn test_shadowing() {
+        let outer = Rc::new(RefCell::new(Environment::new()));
+        outer
+            .borrow_mut()
+            .define("x", Value::Number(1.0), false)
+            .unwrap();
+
+        let mut inner = Environment::new_enclosed(Rc::clone(&outer));
+        inner.define("x", Value::Boolean(true), false).unwrap();
+        inner.assign("x", Value::Boolean(false)).unwrap();
+
+        assert_eq!(inner.get("x"), Some(Value::Boolean(false)));
+        assert_eq!(outer.borrow().get("x"), Some(Value::Number(1.0)));
+    }
+
+    #[test]
+    fn test_bindings() {
+        let outer = Rc::new(RefCell::new(Environment::new()));
+        outer
+            .borrow_mut()
+            .define("x", Value::Number(1.0), false)
+            .unwrap();
+
+        let mut inner = Environment::new_enclosed(Rc::clone(&outer));
+        inner.define("b", Value::Null, true).unwrap();
+        inner.define("a", Value::Boolean(true), false).unwrap();
+
+        assert_eq!(
+            inner.bindings(),
+            vec![
+                (String::from("a"), Value::Boolean(true), false),
+                (String::from("b"), Value::Null, true),
+            ]
+        );
+    }
+}
@@ -0,0 +1,1625 @@
+pub mod environment;
+
+use crate::ast::{
+    BlockStatement, CatchClause, DescribeBlock, ExpressionNode, InfixExpression, MethodBody,
+    MethodDeclaration, Node, Program, QualifiedName, StatementNode, TestDeclaration, TryExpression,
+    VarStatement,
+};
+use crate::evaluator::environment::Environment;
+use crate::lexer2::Lexer;
+use crate::parser::Parser;
+use crate::runtime::natives::{NativeMethod, Natives};
+use crate::runtime::object::{Module, ModuleKind, Object};
+use crate::runtime::pair::Pair;
+use crate::runtime::range::Range;
+use crate::runtime::trace::StackFrame;
+use crate::runtime::value::{Closure, Value};
+use crate::token::{Span, TokenKind};
+use std::cell::RefCell;
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::io::Write;
+use std::rc::Rc;
+
+const LANG: &str = include_str!("../runtime/lang.wlk");
+
+/// Nested method calls allowed before a StackOverflowException is thrown.
+const MAX_DEPTH: usize = 1024;
+
+#[derive(Debug)]
+pub enum Unwind {
+    Return(Value),
+    Throw(Value),
+    Error(String),
+}
+
+pub type EvalResult = Result<Value, Unwind>;
+
+/// An error that escaped evaluation, with the Wollok stack trace of the
+/// point where it was raised (innermost frame first).
+#[derive(Debug, PartialEq, Clone)]
+pub struct RuntimeError {
+    pub message: String,
+    /// Class of the uncaught exception, if what escaped was one.
+    pub exception: Option<String>,
+    pub stack_trace: Vec<StackFrame>,
+}
+
+impl From<String> for RuntimeError {
+    fn from(message: String) -> Self {
+        Self {
+            message,
+            exception: None,
+            stack_trace: vec![],
+        }
+    }
+}
+
+impl Display for RuntimeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)?;
+        for frame in &self.stack_trace {
+            write!(f, "\n    at {}", frame)?;
+        }
+        Ok(())
+    }
+}
+
+struct Frame {
+    receiver: Rc<Object>,
+    method: String,
+    level: usize,
+    description: String,
+    file: String,
+    location: Span,
+}
+
+pub struct Evaluator<'a> {
+    env: Rc<RefCell<Environment>>,
+    object_class: Rc<Module>,
+    natives: Natives,
+    frames: Vec<Frame>,
+    /// Nested method calls, native calls and closure applications, checked
+    /// against MAX_DEPTH. Only methods push a frame, so this can exceed
+    /// frames.len().
+    depth: usize,
+    file: String,
+    entry: String,
+    location: Span,
+    /// Where console.println writes.
+    output: Box<dyn Write + 'a>,
+}
+
+impl<'a> Evaluator<'a> {
+    pub fn new() -> Self {
+        Self::with_output(io::stdout())
+    }
+
+    /// An evaluator whose console writes to `output` instead of stdout.
+    pub fn with_output(output: impl Write + 'a) -> Self {
+        let env = Rc::new(RefCell::new(Environment::new()));
+        let object_class = Rc::new(Module {
+            kind: ModuleKind::Class,
+            name: Some(String::from("Object")),
+            parent: None,
+            mixins: vec![],
+            fields: vec![],
+            methods: vec![],
+            env: Rc::clone(&env),
+            file: String::from("wollok/lang.wlk"),
+        });
+
+        env.borrow_mut()
+            .define("Object", Value::Module(Rc::clone(&object_class)), true)
+            .expect("the global environment starts empty");
+
+        let mut evaluator = Self {
+            env,
+            object_class,
+            natives: Natives::new(),
+            frames: vec![],
+            depth: 0,
+            file: String::from("wollok/lang.wlk"),
+            entry: String::from("top level"),
+            location: Span::default(),
+            output: Box::new(output),
+        };
+
+        let mut parser = Parser::new(Lexer::new(LANG));
+        let lang = parser.parse_program().expect("wollok/lang.wlk parses");
+        evaluator
+            .eval_program(&lang)
+            .expect("wollok/lang.wlk evaluates");
+        evaluator.set_file("<input>");
+
+        evaluator
+    }
+
+    pub fn output(&mut self) -> &mut dyn Write {
+        &mut *self.output
+    }
+
+    /// The global bindings, including the ones from wollok/lang.wlk.
+    pub fn bindings(&self) -> Vec<(String, Value, bool)> {
+        self.env.borrow().bindings()
+    }
+
+    pub fn lookup(&self, name: &str) -> Option<Value> {
+        self.env.borrow().get(name)
+    }
+
+    /// Every message a value understands, user-defined or native, sorted.
+    pub fn message_names(&self, value: &Value) -> Vec<String> {
+        let mut names: Vec<String> = match value {
+            Value::Object(object) => object
+                .module
+                .linearization()
+                .iter()
+                .flat_map(|module| {
+                    let natives = self.natives.names(&module.name());
+                    module
+                        .methods
+                        .iter()
+                        .map(|method| method.name.value.clone())
+                        .chain(natives.into_iter().map(String::from))
+                        .collect::<Vec<String>>()
+                })
+                .collect(),
+            other => self
+                .natives
+                .names(&other.type_name())
+                .into_iter()
+                .chain(self.natives.names("Object"))
+                .map(String::from)
+                .collect(),
+        };
+
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    /// Names the source file that the next programs come from, for stack traces.
+    pub fn set_file(&mut self, file: &str) {
+        self.file = String::from(file);
+    }
+
+    pub fn eval_program(&mut self, program: &Program) -> Result<Value, RuntimeError> {
+        self.location = Span::default();
+        let env = Rc::clone(&self.env);
+        let result = self.eval_statements(&program.statements, &env);
+        self.finish(result)
+    }
+
+    pub fn run_program(
+        &mut self,
+        program: &Program,
+        name: Option<&str>,
+    ) -> Result<Value, RuntimeError> {
+        self.eval_program(program)?;
+
+        let declaration = program.select_program(name)?;
+        self.entry = format!("program {}", declaration.name.value);
+        self.location = declaration.token.span;
+        let env = Rc::clone(&self.env);
+        let result = self.eval_block(&declaration.body, &env);
+        self.finish(result)
+    }
+
+    /// Runs a single test against the declarations already evaluated. Tests
+    /// in a describe run on a fresh instance of it, so its fields and
+    /// initialize are set up again for every test.
+    pub fn run_test(
+        &mut self,
+        describe: Option<&DescribeBlock>,
+        test: &TestDeclaration,
+    ) -> Result<Value, RuntimeError> {
+        self.entry = format!("test {}", test.name.print_string());
+        self.location = test.token.span;
+        let result = self.eval_test(describe, test);
+        self.finish(result)
+    }
+
+    fn eval_test(
+        &mut self,
+        describe: Option<&DescribeBlock>,
+        test: &TestDeclaration,
+    ) -> EvalResult {
+        let describe = match describe {
+            Some(describe) => describe,
+            None => {
+                let env = Rc::clone(&self.env);
+                return self.eval_block(&test.body, &env);
+            }
+        };
+
+        let module = Module {
+            kind: ModuleKind::Object,
+            name: Some(describe.name.value.clone()),
+            parent: Some(Rc::clone(&self.object_class)),
+            mixins: vec![],
+            fields: describe.fields.clone(),
+            methods: describe.methods.clone(),
+            env: Rc::clone(&self.env),
+            file: self.file.clone(),
+        };
+        let fixture = match self.instantiate(Rc::new(module), vec![])? {
+            Value::Object(object) => object,
+            other => return Ok(other),
+        };
+
+        let env = Rc::new(RefCell::new(Environment::new_enclosed(Rc::clone(
+            &fixture.fields,
+        ))));
+        env.borrow_mut()
+            .define("self", Value::Object(Rc::clone(&fixture)), true)
+            .map_err(Unwind::Error)?;
+
+        self.eval_block(&test.body, &env)
+    }
+
+    fn finish(&mut self, result: EvalResult) -> Result<Value, RuntimeError> {
+        self.frames.clear();
+        self.depth = 0;
+
+        match result {
+            Ok(value) | Err(Unwind::Return(value)) => Ok(value),
+            Err(Unwind::Throw(Value::Object(exception))) if exception.is_kind_of("Exception") => {
+                Err(RuntimeError {
+                    message: Self::describe_exception(&exception),
+                    exception: Some(exception.module.name()),
+                    stack_trace: exception.stack_trace.borrow().clone(),
+                })
+            }
+            Err(Unwind::Throw(exception)) => Err(RuntimeError {
+                message: format!("uncaught exception: {}", exception),
+                exception: None,
+                stack_trace: self.stack_trace(),
+            }),
+            Err(Unwind::Error(message)) => Err(RuntimeError {
+                message,
+                exception: None,
+                stack_trace: self.stack_trace(),
+            }),
+        }
+    }
+
+    fn describe_exception(exception: &Object) -> String {
+        let class = exception.module.name();
+        let message = exception.fields.borrow().get("message");
+
+        match message {
+            Some(Value::Null) | None => class,
+            Some(message) if class == "EvaluationError" => message.to_wollok_string(),
+            Some(message) => format!("{}: {}", class, message.to_wollok_string()),
+        }
+    }
+
+    /// Remembers how far evaluation got in the running method (or at the top
+    /// level), so stack traces can point at it.
+    fn mark(&mut self, span: Span) {
+        match self.frames.last_mut() {
+            Some(frame) => frame.location = span,
+            None => self.location = span,
+        }
+    }
+
+    fn stack_trace(&self) -> Vec<StackFrame> {
+        let mut trace: Vec<StackFrame> = self
+            .frames
+            .iter()
+            .rev()
+            .map(|frame| StackFrame {
+                description: frame.description.clone(),
+                file: frame.file.clone(),
+                position: frame.location.start,
+            })
+            .collect();
+
+        trace.push(StackFrame {
+            description: self.entry.clone(),
+            file: self.file.clone(),
+            position: self.location.start,
+        });
+
+        trace
+    }
+
+    /// Builds one of the wollok.lang exceptions, ready to be thrown.
+    fn exception(&mut self, class: &str, message: String) -> Unwind {
+        self.new_exception(
+            class,
+            vec![(String::from("message"), Value::String(message))],
+        )
+    }
+
+    /// Instantiates the exception class with the given fields, for natives
+    /// that need more than a message.
+    pub fn new_exception(&mut self, class: &str, fields: Vec<(String, Value)>) -> Unwind {
+        let module = match self.env.borrow().get(class) {
+            Some(Value::Module(module)) => module,
+            _ => return Unwind::Error(format!("reference {} not found", class)),
+        };
+
+        match self.instantiate(module, fields) {
+            Ok(exception) => Unwind::Throw(exception),
+            Err(unwind) => unwind,
+        }
+    }
+
+    fn eval_statements(
+        &mut self,
+        statements: &[StatementNode],
+        env: &Rc<RefCell<Environment>>,
+    ) -> EvalResult {
+        let mut result = Value::Null;
+
+        for stmt in statements {
+            result = self.eval_statement(stmt, env)?;
+        }
+
+        Ok(result)
+    }
+
+    fn eval_block(&mut self, block: &BlockStatement, env: &Rc<RefCell<Environment>>) -> EvalResult {
+        let block_env = Rc::new(RefCell::new(Environment::new_enclosed(Rc::clone(env))));
+        self.eval_statements(&block.statements, &block_env)
+    }
+
+    fn eval_statement(
+        &mut self,
+        stmt: &StatementNode,
+        env: &Rc<RefCell<Environment>>,
+    ) -> EvalResult {
+        match stmt {
+            StatementNode::Var(var_stmt) => {
+                self.eval_var_statement(var_stmt, env)?;
+                Ok(Value::Null)
+            }
+            StatementNode::Assign(assign) => {
+                let value = self.eval_expression(&assign.value, env)?;
+                self.mark(assign.name.token.span);
+                env.borrow_mut()
+                    .assign(&assign.name.value, value)
+                    .map_err(Unwind::Error)?;
+                Ok(Value::Null)
+            }
+            StatementNode::Return(ret_stmt) => {
+                let value = match &ret_stmt.ret_value {
+                    Some(ret_value) => self.eval_expression(ret_value, env)?,
+                    None => Value::Null,
+                };
+                Err(Unwind::Return(value))
+            }
+            StatementNode::Expression(exp_stmt) => match &exp_stmt.expression {
+                Some(expression) => self.eval_expression(expression, env),
+                None => Ok(Value::Null),
+            },
+            StatementNode::Object(declaration) => {
+                self.mark(declaration.name.token.span);
+                let module = Module {
+                    kind: ModuleKind::Object,
+                    name: Some(declaration.name.value.clone()),
+                    parent: Some(self.resolve_parent(&declaration.parent, env)?),
+                    mixins: self.resolve_mixins(&declaration.mixins, env)?,
+                    fields: declaration.fields.clone(),
+                    methods: declaration.methods.clone(),
+                    env: Rc::clone(env),
+                    file: self.file.clone(),
+                };
+                let object = self.instantiate(Rc::new(module), vec![])?;
+                env.borrow_mut()
+                    .define(&declaration.name.value, object, true)
+                    .map_err(Unwind::Error)?;
+                Ok(Value::Null)
+            }
+            StatementNode::Class(declaration) => {
+                self.mark(declaration.name.token.span);
+                let module = Module {
+                    kind: ModuleKind::Class,
+                    name: Some(declaration.name.value.clone()),
+                    parent: Some(self.resolve_parent(&declaration.parent, env)?),
+                    mixins: self.resolve_mixins(&declaration.mixins, env)?,
+                    fields: declaration.fields.clone(),
+                    methods: declaration.methods.clone(),
+                    env: Rc::clone(env),
+                    file: self.file.clone(),
+                };
+                env.borrow_mut()
+                    .define(
+                        &declaration.name.value,
+                        Value::Module(Rc::new(module)),
+                        true,
+                    )
+                    .map_err(Unwind::Error)?;
+                Ok(Value::Null)
+            }
+            StatementNode::Mixin(declaration) => {
+                self.mark(declaration.name.token.span);
+                let module = Module {
+                    kind: ModuleKind::Mixin,
+                    name: Some(declaration.name.value.clone()),
+                    parent: None,
+                    mixins: self.resolve_mixins(&declaration.mixins, env)?,
+                    fields: declaration.fields.clone(),
+                    methods: declaration.methods.clone(),
+                    env: Rc::clone(env),
+                    file: self.file.clone(),
+                };
+                env.borrow_mut()
+                    .define(
+                        &declaration.name.value,
+                        Value::Module(Rc::new(module)),
+                        true,
+                    )
+                    .map_err(Unwind::Error)?;
+                Ok(Value::Null)
+            }
+            StatementNode::Throw(throw) => {
+                let exception = self.eval_expression(&throw.exception, env)?;
+                self.mark(throw.token.span);
+                Err(Unwind::Throw(exception))
+            }
+            StatementNode::Package(package) => {
+                self.eval_statements(&package.statements, env)?;
+                Ok(Value::Null)
+            }
+            StatementNode::Program(_) | StatementNode::Describe(_) | StatementNode::Test(_) => {
+                Ok(Value::Null)
+            }
+        }
+    }
+
+    fn eval_var_statement(
+        &mut self,
+        var_stmt: &VarStatement,
+        env: &Rc<RefCell<Environment>>,
+    ) -> Result<(), Unwind> {
+        let value = match &var_stmt.value {
+            Some(value) => self.eval_expression(value, env)?,
+            None => Value::Null,
+        };
+
+        self.mark(var_stmt.name.token.span);
+        env.borrow_mut()
+            .define(
+                &var_stmt.name.value,
+                value,
+                var_stmt.token.kind == TokenKind::Const,
+            )
+            .map_err(Unwind::Error)
+    }
+
+    fn eval_expression(
+        &mut self,
+        expression: &ExpressionNode,
+        env: &Rc<RefCell<Environment>>,
+    ) -> EvalResult {
+        match expression {
+            ExpressionNode::IdentifierNode(identifier) => {
+                self.mark(identifier.token.span);
+                let value = env.borrow().get(&identifier.value);
+                value.ok_or_else(|| {
+                    Unwind::Error(format!("reference {} not found", identifier.value))
+                })
+            }
+            ExpressionNode::Integer(integer) => Ok(Value::Number(integer.value as f64)),
+            ExpressionNode::Decimal(decimal) => Ok(Value::Number(decimal.value)),
+            ExpressionNode::String(string) => Ok(Value::String(string.value.clone())),
+            ExpressionNode::Boolean(boolean) => Ok(Value::Boolean(boolean.value)),
+            ExpressionNode::Null(_) => Ok(Value::Null),
+            ExpressionNode::Prefix(prefix) => {
+                let right = self.eval_expression(&prefix.right, env)?;
+                self.mark(prefix.token.span);
+                Self::eval_prefix_operator(&prefix.operator, right)
+            }
+            ExpressionNode::Infix(infix) => self.eval_infix_expression(infix, env),
+            ExpressionNode::SelfNode(_) => {
+                let value = env.borrow().get("self");
+                value.ok_or_else(|| Unwind::Error(String::from("self is not available here")))
+            }
+            ExpressionNode::Super(super_call) => {
+                let arguments = self.eval_expressions(&super_call.arguments, env)?;
+                self.mark(super_call.token.span);
+                self.send_super(arguments)
+            }
+            ExpressionNode::Send(send) => {
+                let receiver = self.eval_expression(&send.receiver, env)?;
+                let arguments = self.eval_expressions(&send.arguments, env)?;
+                self.mark(send.message.token.span);
+                self.send_message(receiver, &send.message.value, arguments)
+            }
+            ExpressionNode::ObjectLiteral(literal) => {
+                let module = Module {
+                    kind: ModuleKind::Object,
+                    name: None,
+                    parent: Some(self.resolve_parent(&literal.parent, env)?),
+                    mixins: self.resolve_mixins(&literal.mixins, env)?,
+                    fields: literal.fields.clone(),
+                    methods: literal.methods.clone(),
+                    env: Rc::clone(env),
+                    file: self.file.clone(),
+                };
+                self.instantiate(Rc::new(module), vec![])
+            }
+            ExpressionNode::New(new) => {
+                self.mark(new.token.span);
+                let name = &new.class.parts.last().unwrap().value;
+                if let Some(constructor) = self.natives.constructor(name) {
+                    if !new.arguments.is_empty() {
+                        return Err(Unwind::Error(format!(
+                            "{} has no fields to initialize",
+                            name
+                        )));
+                    }
+                    return Ok(constructor());
+                }
+
+                let class = Self::resolve_module(&new.class, ModuleKind::Class, env)?;
+                let mut arguments = vec![];
+                for argument in &new.arguments {
+                    let value = self.eval_expression(&argument.value, env)?;
+                    arguments.push((argument.name.value.clone(), value));
+                }
+                self.mark(new.token.span);
+                self.instantiate(class, arguments)
+            }
+            ExpressionNode::List(list) => {
+                let elements = self.eval_expressions(&list.elements, env)?;
+                Ok(Value::List(Rc::new(RefCell::new(elements))))
+            }
+            ExpressionNode::Set(set) => {
+                let mut elements: Vec<Value> = vec![];
+                for element in self.eval_expressions(&set.elements, env)? {
+                    let mut found = false;
+                    for known in &elements {
+                        if self.equal(known, &element)? {
+                            found = true;
+                            break;
+                        }
+                    }
+                    if !found {
+                        elements.push(element);
+                    }
+                }
+                Ok(Value::Set(Rc::new(RefCell::new(elements))))
+            }
+            ExpressionNode::Try(try_exp) => self.eval_try_expression(try_exp, env),
+            ExpressionNode::Range(range) => {
+                let start = self.eval_expression(&range.start, env)?;
+                let end = self.eval_expression(&range.end, env)?;
+                self.mark(range.token.span);
+                match (start, end) {
+                    (Value::Number(start), Value::Number(end))
+                        if start.fract() == 0.0 && end.fract() == 0.0 =>
+                    {
+                        Ok(Value::Range(Range::new(start as i64, end as i64)))
+                    }
+                    (start, end) => Err(Unwind::Error(format!(
+                        "range limits must be integers, got {} and {}",
+                        start, end
+                    ))),
+                }
+            }
+            ExpressionNode::If(if_exp) => {
+                let condition = self.eval_expression(&if_exp.condition, env)?;
+                self.mark(if_exp.token.span);
+                match condition {
+                    Value::Boolean(true) => self.eval_block(&if_exp.consequence, env),
+                    Value::Boolean(false) => match &if_exp.alternative {
+                        Some(alternative) => self.eval_block(alternative, env),
+                        None => Ok(Value::Null),
+                    },
+                    other => Err(Unwind::Error(format!(
+                        "if condition must be a boolean, got {}",
+                        other
+                    ))),
+                }
+            }
+            ExpressionNode::Closure(literal) => Ok(Value::Closure(Rc::new(Closure {
+                literal: literal.clone(),
+                env: Rc::clone(env),
+            }))),
+        }
+    }
+
+    fn eval_expressions(
+        &mut self,
+        expressions: &[ExpressionNode],
+        env: &Rc<RefCell<Environment>>,
+    ) -> Result<Vec<Value>, Unwind> {
+        let mut values = vec![];
+
+        for expression in expressions {
+            values.push(self.eval_expression(expression, env)?);
+        }
+
+        Ok(values)
+    }
+
+    fn eval_prefix_operator(operator: &str, right: Value) -> EvalResult {
+        match (operator, right) {
+            ("!" | "not", Value::Boolean(value)) => Ok(Value::Boolean(!value)),
+            ("-", Value::Number(value)) => Ok(Value::Number(-value)),
+            (operator, right) => Err(Unwind::Error(format!(
+                "{} does not understand {}",
+                right, operator
+            ))),
+        }
+    }
+
+    fn eval_infix_expression(
+        &mut self,
+        infix: &InfixExpression,
+        env: &Rc<RefCell<Environment>>,
+    ) -> EvalResult {
+        let left = self.eval_expression(&infix.left, env)?;
+
+        match infix.token.kind {
+            TokenKind::And | TokenKind::Or => {
+                let short_circuit = infix.token.kind == TokenKind::Or;
+                return match left {
+                    Value::Boolean(value) if value == short_circuit => Ok(left),
+                    Value::Boolean(_) => match self.eval_expression(&infix.right, env)? {
+                        Value::Boolean(value) => Ok(Value::Boolean(value)),
+                        other => Err(Unwind::Error(format!(
+                            "{} does not understand {}",
+                            other, infix.operator
+                        ))),
+                    },
+                    other => Err(Unwind::Error(format!(
+                        "{} does not understand {}",
+                        other, infix.operator
+                    ))),
+                };
+            }
+            _ => {}
+        }
+
+        let right = self.eval_expression(&infix.right, env)?;
+        self.mark(infix.token.span);
+        self.eval_infix_operator(&infix.operator, left, right)
+    }
+
+    pub fn eval_infix_operator(&mut self, operator: &str, left: Value, right: Value) -> EvalResult {
+        match (operator, &left, &right) {
+            (
+                "==" | "!=",
+                Value::Object(_)
+                | Value::List(_)
+                | Value::Set(_)
+                | Value::Dictionary(_)
+                | Value::Pair(_),
+                _,
+            ) => self.send_message(left, operator, vec![right]),
+            ("==", _, _) => Ok(Value::Boolean(left == right)),
+            ("!=", _, _) => Ok(Value::Boolean(left != right)),
+            ("->", _, _) => Ok(Value::Pair(Rc::new(Pair::new(left, right)))),
+            (_, Value::Number(l), Value::Number(r)) => {
+                let (l, r) = (*l, *r);
+                match operator {
+                    "+" => Ok(Value::Number(l + r)),
+                    "-" => Ok(Value::Number(l - r)),
+                    "*" => Ok(Value::Number(l * r)),
+                    "/" | "%" if r == 0.0 => Err(Unwind::Error(String::from("division by zero"))),
+                    "/" => Ok(Value::Number(l / r)),
+                    "%" => Ok(Value::Number(l % r)),
+                    "<" => Ok(Value::Boolean(l < r)),
+                    ">" => Ok(Value::Boolean(l > r)),
+                    "<=" => Ok(Value::Boolean(l <= r)),
+                    ">=" => Ok(Value::Boolean(l >= r)),
+                    _ => self.send_message(left, operator, vec![right]),
+                }
+            }
+            _ => self.send_message(left, operator, vec![right]),
+        }
+    }
+
+    fn eval_try_expression(
+        &mut self,
+        try_exp: &TryExpression,
+        env: &Rc<RefCell<Environment>>,
+    ) -> EvalResult {
+        let result = match self.eval_block(&try_exp.body, env) {
+            Err(Unwind::Error(message)) => match self.exception("EvaluationError", message) {
+                Unwind::Throw(exception) => self.eval_catch(&try_exp.catches, exception, env),
+                other => Err(other),
+            },
+            Err(Unwind::Throw(exception)) => self.eval_catch(&try_exp.catches, exception, env),
+            other => other,
+        };
+
+        if let Some(always) = &try_exp.always {
+            self.eval_block(always, env)?;
+        }
+
+        result
+    }
+
+    fn eval_catch(
+        &mut self,
+        catches: &[CatchClause],
+        exception: Value,
+        env: &Rc<RefCell<Environment>>,
+    ) -> EvalResult {
+        for catch in catches {
+            let matches = match &catch.exception_type {
+                Some(exception_type) => {
+                    let type_name = exception_type.parts.last().unwrap().value.as_str();
+                    match &exception {
+                        Value::Object(object) => object.is_kind_of(type_name),
+                        other => type_name == "Object" || other.type_name() == type_name,
+                    }
+                }
+                None => true,
+            };
+
+            if matches {
+                let catch_env = Rc::new(RefCell::new(Environment::new_enclosed(Rc::clone(env))));
+                catch_env
+                    .borrow_mut()
+                    .define(&catch.parameter.value, exception, true)
+                    .map_err(Unwind::Error)?;
+                return self.eval_block(&catch.body, &catch_env);
+            }
+        }
+
+        Err(Unwind::Throw(exception))
+    }
+
+    fn resolve_module(
+        name: &QualifiedName,
+        kind: ModuleKind,
+        env: &Rc<RefCell<Environment>>,
+    ) -> Result<Rc<Module>, Unwind> {
+        let identifier = &name.parts.last().unwrap().value;
+        let value = env.borrow().get(identifier);
+
+        match value {
+            Some(Value::Module(module)) if module.kind == kind => Ok(module),
+            Some(_) => Err(Unwind::Error(format!(
+                "{} is not a {}",
+                name.print_string(),
+                format!("{:?}", kind).to_lowercase()
+            ))),
+            None => Err(Unwind::Error(format!(
+                "reference {} not found",
+                name.print_string()
+            ))),
+        }
+    }
+
+    fn resolve_parent(
+        &self,
+        parent: &Option<QualifiedName>,
+        env: &Rc<RefCell<Environment>>,
+    ) -> Result<Rc<Module>, Unwind> {
+        match parent {
+            Some(parent) => Self::resolve_module(parent, ModuleKind::Class, env),
+            None => Ok(Rc::clone(&self.object_class)),
+        }
+    }
+
+    fn resolve_mixins(
+        &self,
+        mixins: &[QualifiedName],
+        env: &Rc<RefCell<Environment>>,
+    ) -> Result<Vec<Rc<Module>>, Unwind> {
+        mixins
+            .iter()
+            .map(|mixin| Self::resolve_module(mixin, ModuleKind::Mixin, env))
+            .collect()
+    }
+
+    fn instantiate(
+        &mut self,
+        module: Rc<Module>,
+        mut arguments: Vec<(String, Value)>,
+    ) -> EvalResult {
+        let object = Rc::new(Object::new(Rc::clone(&module)));
+
+        if module.inherits_from("Exception") {
+            *object.stack_trace.borrow_mut() = self.stack_trace();
+        }
+
+        let init_env = Rc::new(RefCell::new(Environment::new_enclosed(Rc::clone(
+            &object.fields,
+        ))));
+        init_env
+            .borrow_mut()
+            .define("self", Value::Object(Rc::clone(&object)), true)
+            .map_err(Unwind::Error)?;
+
+        for ancestor in module.linearization().iter().rev() {
+            for field in &ancestor.fields {
+                let argument = arguments
+                    .iter()
+                    .position(|(name, _)| *name == field.name.value)
+                    .map(|index| arguments.remove(index).1);
+
+                let value = match (argument, &field.value) {
+                    (Some(argument), _) => argument,
+                    (None, Some(value)) => self.eval_expression(value, &init_env)?,
+                    (None, None) => Value::Null,
+                };
+                object
+                    .fields
+                    .borrow_mut()
+                    .define(
+                        &field.name.value,
+                        value,
+                        field.token.kind == TokenKind::Const,
+                    )
+                    .map_err(Unwind::Error)?;
+            }
+        }
+
+        if let Some((name, _)) = arguments.first() {
+            return Err(Unwind::Error(format!(
+                "{} has no field named {}",
+                module.name(),
+                name
+            )));
+        }
+
+        if let Some((level, method)) = object.lookup("initialize", 0) {
+            self.invoke_method(&object, level, &method, vec![])?;
+        }
+
+        Ok(Value::Object(object))
+    }
+
+    pub fn send_message(
+        &mut self,
+        receiver: Value,
+        message: &str,
+        arguments: Vec<Value>,
+    ) -> EvalResult {
+        match &receiver {
+            Value::Object(object) => {
+                if let Some((level, method)) = object.lookup(message, arguments.len()) {
+                    return self.invoke_method(object, level, &method, arguments);
+                }
+
+                let native = object.module.linearization().iter().find_map(|module| {
+                    self.natives
+                        .lookup(&module.name(), message, arguments.len())
+                });
+                if let Some(native) = native {
+                    return self.call_native(native, receiver, arguments);
+                }
+
+                if let Some((level, method)) = object.lookup("messageNotUnderstood", 2) {
+                    let arguments = vec![
+                        Value::String(String::from(message)),
+                        Value::List(Rc::new(RefCell::new(arguments))),
+                    ];
+                    return self.invoke_method(object, level, &method, arguments);
+                }
+            }
+            Value::Closure(closure) if message == "apply" => {
+                return self.apply_closure(closure, arguments);
+            }
+            other => {
+                let native = self
+                    .natives
+                    .lookup(&other.type_name(), message, arguments.len())
+                    .or_else(|| self.natives.lookup("Object", message, arguments.len()));
+                if let Some(native) = native {
+                    return self.call_native(native, receiver, arguments);
+                }
+            }
+        }
+
+        let description = format!(
+            "{} does not understand {}/{}",
+            receiver,
+            message,
+            arguments.len()
+        );
+        Err(self.exception("MessageNotUnderstoodException", description))
+    }
+
+    fn send_super(&mut self, arguments: Vec<Value>) -> EvalResult {
+        let (receiver, message, level) = match self.frames.last() {
+            Some(frame) => (
+                Rc::clone(&frame.receiver),
+                frame.method.clone(),
+                frame.level,
+            ),
+            None => {
+                return Err(Unwind::Error(String::from(
+                    "super can only be used inside a method",
+                )))
+            }
+        };
+
+        match receiver.module.lookup(&message, arguments.len(), level + 1) {
+            Some((level, method)) => self.invoke_method(&receiver, level, &method, arguments),
+            None => Err(Unwind::Error(format!(
+                "{} has no super implementation of {}/{}",
+                Value::Object(receiver),
+                message,
+                arguments.len()
+            ))),
+        }
+    }
+
+    fn invoke_method(
+        &mut self,
+        object: &Rc<Object>,
+        level: usize,
+        method: &MethodDeclaration,
+        arguments: Vec<Value>,
+    ) -> EvalResult {
+        let env = Rc::new(RefCell::new(Environment::new_enclosed(Rc::clone(
+            &object.fields,
+        ))));
+        env.borrow_mut()
+            .define("self", Value::Object(Rc::clone(object)), true)
+            .map_err(Unwind::Error)?;
+
+        for (parameter, argument) in method.parameters.iter().zip(arguments) {
+            env.borrow_mut()
+                .define(&parameter.value, argument, false)
+                .map_err(Unwind::Error)?;
+        }
+
+        self.enter()?;
+
+        let module = &object.module.linearization()[level];
+        let parameters: Vec<&str> = method
+            .parameters
+            .iter()
+            .map(|parameter| parameter.value.as_str())
+            .collect();
+
+        self.frames.push(Frame {
+            receiver: Rc::clone(object),
+            method: method.name.value.clone(),
+            level,
+            description: format!(
+                "{}.{}({})",
+                module.name.as_deref().unwrap_or("anonymous object"),
+                method.name.value,
+                parameters.join(", ")
+            ),
+            file: module.file.clone(),
+            location: method.token.span,
+        });
+
+        let result = match &method.body {
+            MethodBody::Abstract => Err(Unwind::Error(format!(
+                "method {} is abstract",
+                method.name.value
+            ))),
+            MethodBody::Expression(expression) => self.eval_expression(expression, &env),
+            MethodBody::Block(block) => match self.eval_statements(&block.statements, &env) {
+                Ok(_) => Ok(Value::Null),
+                Err(Unwind::Return(value)) => Ok(value),
+                Err(other) => Err(other),
+            },
+        };
+
+        // Turn evaluation errors into exceptions while this frame is still on
+        // the stack, so their trace starts where they happened.
+        let result = match result {
+            Err(Unwind::Error(message)) => Err(self.exception("EvaluationError", message)),
+            other => other,
+        };
+
+        self.frames.pop();
+        self.depth -= 1;
+        result
+    }
+
+    /// Natives count as nested calls too: printing a list that contains
+    /// itself goes from native to native without entering a method.
+    fn call_native(
+        &mut self,
+        native: NativeMethod,
+        receiver: Value,
+        arguments: Vec<Value>,
+    ) -> EvalResult {
+        self.enter()?;
+        let result = native(self, receiver, arguments);
+        self.depth -= 1;
+        result
+    }
+
+    /// Counts one more nested call, throwing StackOverflowException before
+    /// the Rust stack runs out.
+    fn enter(&mut self) -> Result<(), Unwind> {
+        if self.depth >= MAX_DEPTH {
+            let description = format!("stack overflow: more than {} nested calls", MAX_DEPTH);
+            return Err(self.exception("StackOverflowException", description));
+        }
+
+        self.depth += 1;
+        Ok(())
+    }
+
+    pub fn apply(&mut self, closure: &Value, arguments: Vec<Value>) -> EvalResult {
+        match closure {
+            Value::Closure(closure) => self.apply_closure(closure, arguments),
+            other => Err(Unwind::Error(format!("expected a closure, got {}", other))),
+        }
+    }
+
+    /// Whether two values are equal as Wollok sees it, sending == so that
+    /// objects that override equals are compared their way.
+    pub fn equal(&mut self, left: &Value, right: &Value) -> Result<bool, Unwind> {
+        match self.send_message(left.clone(), "==", vec![right.clone()])? {
+            Value::Boolean(equal) => Ok(equal),
+            other => Err(Unwind::Error(format!(
+                "== must return a boolean, got {}",
+                other
+            ))),
+        }
+    }
+
+    /// The printString of a value, for callers outside the evaluator.
+    pub fn describe(&mut self, value: &Value) -> Result<String, RuntimeError> {
+        let result = self.print_string(value).map(Value::String);
+        match self.finish(result)? {
+            Value::String(string) => Ok(string),
+            other => Ok(other.to_string()),
+        }
+    }
+
+    pub fn print_string(&mut self, value: &Value) -> Result<String, Unwind> {
+        match self.send_message(value.clone(), "printString", vec![])? {
+            Value::String(string) => Ok(string),
+            other => Err(Unwind::Error(format!(
+                "printString must return a string, got {}",
+                other
+            ))),
+        }
+    }
+
+    fn apply_closure(&mut self, closure: &Closure, arguments: Vec<Value>) -> EvalResult {
+        let parameters = &closure.literal.parameters;
+
+        if parameters.len() != arguments.len() {
+            return Err(Unwind::Error(format!(
+                "closure {} expects {} arguments, got {}",
+                closure.literal.print_string(),
+                parameters.len(),
+                arguments.len()
+            )));
+        }
+
+        let env = Rc::new(RefCell::new(Environment::new_enclosed(Rc::clone(
+            &closure.env,
+        ))));
+        for (parameter, argument) in parameters.iter().zip(arguments) {
+            env.borrow_mut()
+                .define(&parameter.value, argument, false)
+                .map_err(Unwind::Error)?;
+        }
+
+        self.enter()?;
+        let result = self.eval_statements(&closure.literal.body.statements, &env);
+        self.depth -= 1;
+
+        match result {
+            Err(Unwind::Return(value)) => Ok(value),
+            other => other,
+        }
+    }
+}
+
+impl Default for Evaluator<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer2::Lexer;
+    use crate::parser::Parser;
+
+    fn eval(input: &str) -> Result<Value, RuntimeError> {
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program().unwrap();
+        assert!(
+            parser.errors().is_empty(),
+            "parser errors: {:?}",
+            parser.errors()
+        );
+
+        Evaluator::new().eval_program(&program)
+    }
+
+    fn check(tests: Vec<(&str, &str)>) {
+        for (input, expected) in tests {
+            match eval(input) {
+                Ok(value) => assert_eq!(value.to_string(), expected, "input: {}", input),
+                Err(err) => panic!("input {} failed: {}", input, err),
+            }
+        }
+    }
+
+    fn check_errors(tests: Vec<(&str, &str)>) {
+        for (input, expected) in tests {
+            match eval(input) {
+                Ok(value) => panic!("input {} evaluated to {}", input, value),
+                Err(err) => assert_eq!(err.message, expected, "input: {}", input),
+            }
+        }
+    }
+
+    #[test]
+    fn test_literals() {
+        check(vec![
+            ("5", "5"),
+            ("2.5", "2.5"),
+            ("\"hello\"", "\"hello\""),
+            ("true", "true"),
+            ("null", "null"),
+            ("[1, 2, 3]", "[1, 2, 3]"),
+            ("#{1, 2, 1}", "#{1, 2}"),
+            ("1..4", "1..4"),
+            ("1 -> 2", "1 -> 2"),
+        ]);
+    }
+
+    #[test]
+    fn test_operators() {
+        check(vec![
+            ("1 + 2 * 3", "7"),
+            ("(1 + 2) * 3", "9"),
+            ("7 / 2", "3.5"),
+            ("7 % 2", "1"),
+            ("-5 + 2", "-3"),
+            ("1 < 2", "true"),
+            ("2 >= 3", "false"),
+            ("1 == 1", "true"),
+            ("[1, 2] == [1, 2]", "true"),
+            ("1 != 2", "true"),
+            ("!true", "false"),
+            ("not false", "true"),
+            ("true && false", "false"),
+            ("false or true", "true"),
+            ("\"a\" + 1", "\"a1\""),
+            ("\"a\" < \"b\"", "true"),
+        ]);
+    }
+
+    #[test]
+    fn test_short_circuit() {
+        check(vec![
+            ("false && undefined", "false"),
+            ("true || undefined", "true"),
+        ]);
+    }
+
+    #[test]
+    fn test_variables() {
+        check(vec![
+            ("var a = 1 a", "1"),
+            ("var a = 1 a = a + 1 a", "2"),
+            ("var a a", "null"),
+            ("const a = 1 if (true) { const a = 2 } a", "1"),
+        ]);
+    }
+
+    #[test]
+    fn test_if_expressions() {
+        check(vec![
+            ("if (true) { 10 }", "10"),
+            ("if (false) { 10 }", "null"),
+            ("if (1 > 2) { 10 } else { 20 }", "20"),
+        ]);
+    }
+
+    #[test]
+    fn test_objects() {
+        check(vec![
+            (
+                "object counter { var count = 0 method inc() { count = count + 1 return self } method count() = count } counter.inc().inc().count()",
+                "2",
+            ),
+            ("object pepita { } pepita", "pepita"),
+            (
+                "object pepita { const energy = 100 method fly(km) { return energy - km } } pepita.fly(30)",
+                "70",
+            ),
+            ("object pepita { method eat() { 10 } } pepita.eat()", "null"),
+            ("const o = object { method value() = 4 } o.value()", "4"),
+            ("const o = object { } o", "an Object"),
+        ]);
+    }
+
+    #[test]
+    fn test_classes() {
+        check(vec![
+            (
+                "class Ave { var energia = 10 method energia() = energia } new Ave().energia()",
+                "10",
+            ),
+            (
+                "class Ave { var energia = 10 method energia() = energia } new Ave(energia = 3).energia()",
+                "3",
+            ),
+            ("class Ave { } new Ave()", "an Ave"),
+            ("class Golondrina { } new Golondrina()", "a Golondrina"),
+            ("class Ave { } Ave", "Ave"),
+            (
+                "class Ave { const nombre method nombre() = nombre } new Ave(nombre = \"pepita\").nombre()",
+                "\"pepita\"",
+            ),
+            (
+                "class Ave { var energia = 0 method initialize() { energia = 100 } method energia() = energia } new Ave().energia()",
+                "100",
+            ),
+            (
+                "class Ave { var a = 1 var b = a + 1 method b() = b } new Ave().b()",
+                "2",
+            ),
+            (
+                "class Contador { var n = 0 method inc() { n = n + 1 } method n() = n } const a = new Contador() const b = new Contador() a.inc() a.inc() b.inc() a.n() * 10 + b.n()",
+                "21",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_inheritance() {
+        check(vec![
+            (
+                "class Ave { var energia = 10 method volar() = energia - 1 } class Golondrina inherits Ave { } new Golondrina().volar()",
+                "9",
+            ),
+            (
+                "class Ave { method volar() = 1 } class Golondrina inherits Ave { override method volar() = super() + 10 } new Golondrina().volar()",
+                "11",
+            ),
+            (
+                "class A { method m() = \"a\" } class B inherits A { override method m() = super() + \"b\" } class C inherits B { override method m() = super() + \"c\" } new C().m()",
+                "\"abc\"",
+            ),
+            (
+                "class A { method m() = self.n() method n() = 1 } class B inherits A { override method n() = 2 } new B().m()",
+                "2",
+            ),
+            (
+                "class Ave { var energia = 5 } class Golondrina inherits Ave { method energia() = energia } new Golondrina(energia = 7).energia()",
+                "7",
+            ),
+            (
+                "class Ave { method volar() = 1 } object pepita inherits Ave { override method volar() = super() * 3 } pepita.volar()",
+                "3",
+            ),
+            (
+                "class Ave { method volar() = 1 } const o = object inherits Ave { } o.volar()",
+                "1",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_mixins() {
+        check(vec![
+            (
+                "mixin Volador { method volar() = \"vuela\" } class Ave mixed with Volador { } new Ave().volar()",
+                "\"vuela\"",
+            ),
+            (
+                "mixin Volador { var altura = 2 method altura() = altura } object pepita mixed with Volador { } pepita.altura()",
+                "2",
+            ),
+            (
+                "mixin A { method m() = \"a\" } mixin B { method m() = \"b\" } class C mixed with A and B { } new C().m()",
+                "\"b\"",
+            ),
+            (
+                "mixin Ruidoso { override method hablar() = super() + \"!\" } class Ave { method hablar() = \"pio\" } class Loro inherits Ave mixed with Ruidoso { } new Loro().hablar()",
+                "\"pio!\"",
+            ),
+            (
+                "mixin Ruidoso { method hablar() = \"pio\" } mixin Griton mixed with Ruidoso { override method hablar() = super() + \"!\" } class Loro mixed with Griton { } new Loro().hablar()",
+                "\"pio!\"",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_message_not_understood() {
+        check(vec![
+            (
+                "object proxy { method messageNotUnderstood(name, args) = name } proxy.hola()",
+                "\"hola\"",
+            ),
+            (
+                "object proxy { method messageNotUnderstood(name, args) = args } proxy.hola(1, 2)",
+                "[1, 2]",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_closures() {
+        check(vec![
+            ("const add = { a, b => a + b } add.apply(1, 2)", "3"),
+            ("const ten = { 10 } ten.apply()", "10"),
+            ("var n = 1 const inc = { n = n + 1 } inc.apply() n", "2"),
+            ("{ x => x * 2 }", "{ x => (x * 2) }"),
+        ]);
+    }
+
+    #[test]
+    fn test_try_expressions() {
+        check(vec![
+            ("try { throw 1 } catch e { e + 1 }", "2"),
+            ("try { 5 } catch e { 0 }", "5"),
+            (
+                "try { throw \"boom\" } catch e : Number { 1 } catch e : String { 2 }",
+                "2",
+            ),
+            (
+                "var a = 0 try { throw 1 } catch e { a = 1 } then always { a = a + 10 } a",
+                "11",
+            ),
+            (
+                "try { try { throw 1 } catch e : String { 2 } } catch e { 3 }",
+                "3",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_exceptions() {
+        check(vec![
+            (
+                "try { throw new DomainException(message = \"sin energia\") } catch e : DomainException { e.message() }",
+                "\"sin energia\"",
+            ),
+            (
+                "try { throw new DomainException(message = \"x\") } catch e : Exception { 1 }",
+                "1",
+            ),
+            (
+                "class SinEnergia inherits DomainException { } try { throw new SinEnergia() } catch e : MessageNotUnderstoodException { 1 } catch e : DomainException { 2 }",
+                "2",
+            ),
+            (
+                "var log = \"\" try { throw new Exception() } catch e : Exception { log = log + \"c\" } then always { log = log + \"t\" } log",
+                "\"ct\"",
+            ),
+            (
+                "var log = \"\" try { try { throw new Exception() } then always { log = \"t\" } } catch e { 0 } log",
+                "\"t\"",
+            ),
+            (
+                "object a { } try { a.fly() } catch e : MessageNotUnderstoodException { e.message() }",
+                "\"a does not understand fly/0\"",
+            ),
+            (
+                "try { 1 / 0 } catch e : EvaluationError { e.message() }",
+                "\"division by zero\"",
+            ),
+        ]);
+
+        check_errors(vec![
+            (
+                "throw new DomainException(message = \"sin energia\")",
+                "DomainException: sin energia",
+            ),
+            ("throw new Exception()", "Exception"),
+            (
+                "class A { method m() = 1 / 0 } new A().m()",
+                "division by zero",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_stack_traces() {
+        let input = "class Ave {\n  method volar(km) {\n    throw new DomainException(message = \"cansada\")\n  }\n}\nobject pepita {\n  method viajar() = new Ave().volar(10)\n}\nprogram main {\n  pepita.viajar()\n}";
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program().unwrap();
+
+        let mut evaluator = Evaluator::new();
+        evaluator.set_file("pepita.wpgm");
+        let err = evaluator.run_program(&program, None).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "DomainException: cansada\n    at Ave.volar(km) [pepita.wpgm:3:11]\n    at pepita.viajar() [pepita.wpgm:7:31]\n    at program main [pepita.wpgm:10:10]"
+        );
+    }
+
+    #[test]
+    fn test_trace_positions() {
+        let tests = vec![
+            ("1 + 1\n  nadie", "top level [<input>:2:3]"),
+            ("const x = 1\nx = 2", "top level [<input>:2:1]"),
+            (
+                "object a { method m() {\n  falta = 1 } }\na.m()",
+                "a.m() [<input>:2:3]",
+            ),
+            ("-\"a\"", "top level [<input>:1:1]"),
+        ];
+
+        for (input, expected) in tests {
+            let err = eval(input).unwrap_err();
+            assert_eq!(err.stack_trace[0].to_string(), expected, "input={}", input);
+        }
+    }
+
+    #[test]
+    fn test_stack_overflow() {
+        // Each nested Wollok call takes several Rust frames, so give the
+        // evaluator the room a real program would have.
+        let handle = std::thread::Builder::new()
+            .stack_size(64 * 1024 * 1024)
+            .spawn(|| {
+                let input = "object r { method loop(n) = self.loop(n + 1) } r.loop(0)";
+                let err = eval(input).unwrap_err();
+                assert_eq!(
+                    err.message,
+                    "StackOverflowException: stack overflow: more than 1024 nested calls"
+                );
+                assert_eq!(err.stack_trace.len(), MAX_DEPTH + 1);
+                assert_eq!(err.stack_trace[0].to_string(), "r.loop(n) [<input>:1:34]");
+
+                check(vec![
+                    (
+                        "object r { method loop(n) = self.loop(n + 1) } try { r.loop(0) } catch e : StackOverflowException { 1 }",
+                        "1",
+                    ),
+                    (
+                        "var f = null f = { n => f.apply(n + 1) } try { f.apply(0) } catch e : StackOverflowException { e.message() }",
+                        "\"stack overflow: more than 1024 nested calls\"",
+                    ),
+                    (
+                        "object r { method loop(l) = l.map({ x => self.loop(l) }) } try { r.loop([1]) } catch e : StackOverflowException { 2 }",
+                        "2",
+                    ),
+                    (
+                        "const l = [1] l.add(l) try { l.toString() } catch e : StackOverflowException { 3 }",
+                        "3",
+                    ),
+                    (
+                        "const d = new Dictionary() d.put(1, d) try { d.printString() } catch e : StackOverflowException { 4 }",
+                        "4",
+                    ),
+                    (
+                        "const l = [1] l.add(l) const m = [1] m.add(m) try { l == m } catch e : StackOverflowException { 5 }",
+                        "5",
+                    ),
+                ]);
+            })
+            .unwrap();
+
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_run_test() {
+        let input = r#"
+            object contador { var n = 0 method sumar() { n = n + 1 } method n() = n }
+            test "suelto" { contador.sumar() contador.n() }
+            describe "contador" {
+                var inicial = 0
+                method initialize() { inicial = 5 }
+                method doble() = inicial * 2
+                test "fixture" { inicial = inicial + 1 self.doble() }
+                test "falla" { throw new DomainException(message = "mal") }
+            }
+        "#;
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program().unwrap();
+        let describe = match &program.statements[2] {
+            StatementNode::Describe(describe) => describe,
+            other => panic!("stmt is not DescribeBlock. got={:?}", other),
+        };
+        let loose = match &program.statements[1] {
+            StatementNode::Test(test) => test,
+            other => panic!("stmt is not TestDeclaration. got={:?}", other),
+        };
+
+        let mut evaluator = Evaluator::new();
+        evaluator.eval_program(&program).unwrap();
+        let result = evaluator.run_test(None, loose);
+        assert_eq!(result.unwrap().to_string(), "1");
+
+        for _ in 0..2 {
+            let result = evaluator.run_test(Some(describe), &describe.tests[0]);
+            assert_eq!(result.unwrap().to_string(), "12");
+        }
+
+        let err = evaluator
+            .run_test(Some(describe), &describe.tests[1])
+            .unwrap_err();
+        assert_eq!(err.message, "DomainException: mal");
+        assert_eq!(err.exception.as_deref(), Some("DomainException"));
+        assert_eq!(
+            err.stack_trace[0].to_string(),
+            "test \"falla\" [<input>:9:38]"
+        );
+    }
+
+    #[test]
+    fn test_message_names() {
+        let input =
+            "class Ave { method volar() = 1 } object pepita inherits Ave { method cantar() = 2 }";
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program().unwrap();
+
+        let mut evaluator = Evaluator::new();
+        evaluator.eval_program(&program).unwrap();
+
+        let names = evaluator.message_names(&evaluator.lookup("pepita").unwrap());
+        for name in ["cantar", "volar", "equals", "printString"] {
+            assert!(names.contains(&String::from(name)), "missing {}", name);
+        }
+
+        let names = evaluator.message_names(&Value::Number(1.0));
+        assert!(names.contains(&String::from("even")));
+        assert!(names.contains(&String::from("toString")));
+        assert!(!names.contains(&String::from("size")));
+    }
+
+    #[test]
+    fn test_errors() {
+        check_errors(vec![
+            ("a", "reference a not found"),
+            ("const a = 1 a = 2", "cannot modify constant a"),
+            ("1 / 0", "division by zero"),
+            (
+                "true + 1",
+                "MessageNotUnderstoodException: true does not understand +/1",
+            ),
+            ("if (1) { 2 }", "if condition must be a boolean, got 1"),
+            (
+                "object a { } a.fly()",
+                "MessageNotUnderstoodException: a does not understand fly/0",
+            ),
+            (
+                "{ x => x }.apply()",
+                "closure { x => x } expects 1 arguments, got 0",
+            ),
+            ("throw 5", "uncaught exception: 5"),
+            (
+                "class Ave { } new Ave(peso = 1)",
+                "Ave has no field named peso",
+            ),
+            ("object pepita { } new pepita()", "pepita is not a class"),
+            ("mixin M { } class A inherits M { }", "M is not a class"),
+            ("class A { } class B mixed with A { }", "A is not a mixin"),
+            ("class A { } mixin M mixed with A { }", "A is not a mixin"),
+            ("new Ave()", "reference Ave not found"),
+            (
+                "class A { method m() = super() } new A().m()",
+                "an A has no super implementation of m/0",
+            ),
+            ("class A { method m() } new A().m()", "method m is abstract"),
+            ("1.5..3", "range limits must be integers, got 1.5 and 3"),
+        ]);
+    }
+
+    #[test]
+    fn test_run_program() {
+        let input = "object calc { method double(n) = n * 2 } program main { const x = calc.double(4) x + 1 } program other { 0 }";
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program().unwrap();
+
+        let result = Evaluator::new().run_program(&program, Some("main"));
+        assert_eq!(result.unwrap().to_string(), "9");
+
+        let result = Evaluator::new().run_program(&program, None);
+        assert_eq!(
+            result.unwrap_err().message,
+            "several programs found, choose one of: main, other"
+        );
+    }
+}
@@ -1,10 +1,13 @@
-use crate::token::{lookup_ident, Token, TokenKind};
+use crate::token::{lookup_ident, Position, Span, Token, TokenKind};
 
+#[derive(Clone)]
 pub struct Lexer {
     input: Vec<char>,
     position: usize,
     read_position: usize,
     ch: char,
+    line: usize,
+    column: usize,
 }
 
 impl Lexer {
@@ -14,6 +17,8 @@ impl Lexer {
             position: 0,
             read_position: 0,
             ch: Default::default(),
+            line: 1,
+            column: 0,
         };
 
         lex.read_char();
@@ -21,6 +26,13 @@ impl Lexer {
     }
 
     fn read_char(&mut self) {
+        if self.ch == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+
         self.ch = if self.read_position >= self.input.len() {
             '\0'
         } else {
@@ -32,33 +44,72 @@ impl Lexer {
 
     pub fn next_token(&mut self) -> Token {
         self.skip_whitespaces();
-        let token = match self.ch {
+
+        let start = self.current_position();
+        let mut token = self.read_token();
+        token.span = Span {
+            start,
+            end: self.current_position(),
+        };
+
+        token
+    }
+
+    fn current_position(&self) -> Position {
+        Position {
+            line: self.line,
+            column: self.column,
+        }
+    }
+
+    fn read_token(&mut self) -> Token {
+        match self.ch {
+            '=' if self.peek_char() == '>' => {
+                self.create_token('>', TokenKind::FatArrow, TokenKind::Assign, "=>", "=")
+            }
             '=' => self.create_token('=', TokenKind::Eq, TokenKind::Assign, "==", "="),
             '!' => self.create_token('=', TokenKind::NotEq, TokenKind::Bang, "!=", "!"),
-            '+' | '-' | '*' | '/' | ':' | ';' | ',' | '(' | ')' | '[' | ']' | '{' | '}' | '#'
-            | '>' | '<' => {
+            '.' => self.create_token('.', TokenKind::DotDot, TokenKind::Dot, "..", "."),
+            '-' => self.create_token('>', TokenKind::Arrow, TokenKind::Minus, "->", "-"),
+            '>' => self.create_token('=', TokenKind::GreaterEq, TokenKind::GreaterThan, ">=", ">"),
+            '<' => self.create_token('=', TokenKind::LessEq, TokenKind::LessThan, "<=", "<"),
+            '&' => self.create_token('&', TokenKind::And, TokenKind::Error, "&&", "&"),
+            '|' => self.create_token('|', TokenKind::Or, TokenKind::Error, "||", "|"),
+            '+' | '*' | '/' | '%' | ':' | ';' | ',' | '(' | ')' | '[' | ']' | '{' | '}' | '#' => {
                 let t = Lexer::new_token(Lexer::match_token_kind(self.ch), self.ch);
                 self.read_char();
                 t
             }
+            '"' | '\'' => self.read_string(),
             '\0' => Token {
                 kind: TokenKind::EOF,
                 literal: "".to_string(),
+                ..Default::default()
             },
             ch if Lexer::is_letter(ch) => {
                 let literal = self.read_identifier();
                 let kind = lookup_ident(&literal);
-                Token { kind, literal }
+                Token {
+                    kind,
+                    literal,
+                    ..Default::default()
+                }
             }
             ch if Lexer::is_digit(ch) => {
                 let kind = TokenKind::Number;
                 let literal = self.read_number();
-                Token { kind, literal }
+                Token {
+                    kind,
+                    literal,
+                    ..Default::default()
+                }
             }
-            _ => Lexer::new_token(TokenKind::Error, self.ch),
-        };
-
-        token
+            _ => {
+                let t = Lexer::new_token(TokenKind::Error, self.ch);
+                self.read_char();
+                t
+            }
+        }
     }
 
     fn create_token(
@@ -74,11 +125,13 @@ impl Lexer {
             Token {
                 kind: match_kind,
                 literal: String::from(match_lit),
+                ..Default::default()
             }
         } else {
             Token {
                 kind: no_match_kind,
                 literal: String::from(no_match_lit),
+                ..Default::default()
             }
         };
         self.read_char();
@@ -91,6 +144,7 @@ impl Lexer {
             '-' => TokenKind::Minus,
             '*' => TokenKind::Multiply,
             '/' => TokenKind::Divide,
+            '%' => TokenKind::Modulo,
             '=' => TokenKind::Assign,
             ':' => TokenKind::Colon,
             ';' => TokenKind::SemiColon,
@@ -102,9 +156,7 @@ impl Lexer {
             '{' => TokenKind::LeftBrace,
             '}' => TokenKind::RightBrace,
             '!' => TokenKind::Bang,
-            '#' => TokenKind::Slash,
-            '>' => TokenKind::GreaterThan,
-            '<' => TokenKind::LessThan,
+            '#' => TokenKind::Hash,
             _ => TokenKind::Error,
         }
     }
@@ -120,23 +172,24 @@ impl Lexer {
     }
 
     fn peek_char(&self) -> char {
-        return if self.read_position >= self.input.len() {
+        if self.read_position >= self.input.len() {
             '\0'
         } else {
             self.input[self.read_position]
-        };
+        }
     }
 
     fn new_token(kind: TokenKind, ch: char) -> Token {
         Token {
             kind,
             literal: ch.to_string(),
+            ..Default::default()
         }
     }
 
     fn read_identifier(&mut self) -> String {
         let mut identifier = String::new();
-        while Lexer::is_letter(self.ch) {
+        while Lexer::is_letter(self.ch) || Lexer::is_digit(self.ch) {
             identifier.push(self.ch);
             self.read_char();
         }
@@ -147,12 +200,60 @@ impl Lexer {
         ch.is_numeric()
     }
 
+    fn read_string(&mut self) -> Token {
+        let quote = self.ch;
+        let mut literal = String::new();
+
+        self.read_char();
+        while self.ch != quote {
+            if self.ch == '\0' {
+                return Token {
+                    kind: TokenKind::Error,
+                    literal,
+                    ..Default::default()
+                };
+            }
+
+            if self.ch == '\\' {
+                self.read_char();
+                match self.ch {
+                    'n' => literal.push('\n'),
+                    't' => literal.push('\t'),
+                    'r' => literal.push('\r'),
+                    '\0' => continue,
+                    other => literal.push(other),
+                }
+            } else {
+                literal.push(self.ch);
+            }
+            self.read_char();
+        }
+        self.read_char();
+
+        Token {
+            kind: TokenKind::String,
+            literal,
+            ..Default::default()
+        }
+    }
+
     fn read_number(&mut self) -> String {
         let mut num = String::from("");
         while Lexer::is_digit(self.ch) {
             num.push(self.ch);
             self.read_char();
         }
+
+        // Only a dot followed by a digit is a decimal point: `1..5` is a range
+        // and `1.even()` is a message send.
+        if self.ch == '.' && Lexer::is_digit(self.peek_char()) {
+            num.push(self.ch);
+            self.read_char();
+            while Lexer::is_digit(self.ch) {
+                num.push(self.ch);
+                self.read_char();
+            }
+        }
         num
     }
 }
@@ -188,290 +289,357 @@ mod test {
             Token {
                 kind: TokenKind::Var,
                 literal: "var".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Identifier,
                 literal: "five".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Assign,
                 literal: "=".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Number,
                 literal: "5".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::SemiColon,
                 literal: ";".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Const,
                 literal: "const".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Identifier,
                 literal: "six".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Assign,
                 literal: "=".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Number,
                 literal: "6".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::SemiColon,
                 literal: ";".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Var,
                 literal: "var".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Identifier,
                 literal: "add".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Assign,
                 literal: "=".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::LeftBrace,
                 literal: "{".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Identifier,
                 literal: "x".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Comma,
                 literal: ",".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Identifier,
                 literal: "y".to_string(),
+                ..Default::default()
             },
             Token {
-                kind: TokenKind::Assign,
-                literal: "=".to_string(),
-            },
-            Token {
-                kind: TokenKind::GreaterThan,
-                literal: ">".to_string(),
+                kind: TokenKind::FatArrow,
+                literal: "=>".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Identifier,
                 literal: "x".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Plus,
                 literal: "+".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Identifier,
                 literal: "y".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::RightBrace,
                 literal: "}".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::SemiColon,
                 literal: ";".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Var,
                 literal: "var".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Identifier,
                 literal: "result".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Assign,
                 literal: "=".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Identifier,
                 literal: "add".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::LeftParen,
                 literal: "(".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Identifier,
                 literal: "five".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Comma,
                 literal: ",".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Identifier,
                 literal: "six".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::RightParen,
                 literal: ")".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::SemiColon,
                 literal: ";".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Bang,
                 literal: "!".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Minus,
                 literal: "-".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Divide,
                 literal: "/".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Multiply,
                 literal: "*".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Number,
                 literal: "5".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::SemiColon,
                 literal: ";".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Number,
                 literal: "5".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::LessThan,
                 literal: "<".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Number,
                 literal: "10".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::GreaterThan,
                 literal: ">".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Number,
                 literal: "5".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::SemiColon,
                 literal: ";".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::If,
                 literal: "if".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::LeftParen,
                 literal: "(".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Number,
                 literal: "10".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::GreaterThan,
                 literal: ">".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Number,
                 literal: "5".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::RightParen,
                 literal: ")".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::LeftBrace,
                 literal: "{".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Return,
                 literal: "return".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::True,
                 literal: "true".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::SemiColon,
                 literal: ";".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::RightBrace,
                 literal: "}".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Else,
                 literal: "else".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::LeftBrace,
                 literal: "{".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Return,
                 literal: "return".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::False,
                 literal: "false".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::SemiColon,
                 literal: ";".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::RightBrace,
                 literal: "}".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Number,
                 literal: "10".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Eq,
                 literal: "==".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Number,
                 literal: "10".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::SemiColon,
                 literal: ";".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Number,
                 literal: "10".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::NotEq,
                 literal: "!=".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Number,
                 literal: "9".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::SemiColon,
                 literal: ";".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::EOF,
                 literal: "".to_string(),
+                ..Default::default()
             },
         ];
 
@@ -486,34 +654,217 @@ mod test {
             Token {
                 kind: TokenKind::Assign,
                 literal: "=".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Plus,
                 literal: "+".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::LeftParen,
                 literal: "(".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::RightParen,
                 literal: ")".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::LeftBrace,
                 literal: "{".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::RightBrace,
                 literal: "}".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::Comma,
                 literal: ",".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::SemiColon,
+                literal: ";".to_string(),
+                ..Default::default()
+            },
+        ];
+
+        let mut lexer = Lexer::new(input);
+        exec_assert(expected, &mut lexer);
+    }
+
+    #[test]
+    fn test_object_keywords() {
+        let input = "object pepita { override method volar() = self.energia() + super() }";
+        let expected: Vec<Token> = vec![
+            Token {
+                kind: TokenKind::Object,
+                literal: "object".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Identifier,
+                literal: "pepita".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::LeftBrace,
+                literal: "{".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Override,
+                literal: "override".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Method,
+                literal: "method".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Identifier,
+                literal: "volar".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::LeftParen,
+                literal: "(".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::RightParen,
+                literal: ")".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Assign,
+                literal: "=".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Self_,
+                literal: "self".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Dot,
+                literal: ".".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Identifier,
+                literal: "energia".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::LeftParen,
+                literal: "(".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::RightParen,
+                literal: ")".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Plus,
+                literal: "+".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Super,
+                literal: "super".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::LeftParen,
+                literal: "(".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::RightParen,
+                literal: ")".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::RightBrace,
+                literal: "}".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::EOF,
+                literal: "".to_string(),
+                ..Default::default()
+            },
+        ];
+
+        let mut lexer = Lexer::new(input);
+        exec_assert(expected, &mut lexer);
+    }
+
+    #[test]
+    fn test_class_keywords() {
+        let input = "class Golondrina inherits Ave mixed with Volador; mixin new";
+        let expected: Vec<Token> = vec![
+            Token {
+                kind: TokenKind::Class,
+                literal: "class".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Identifier,
+                literal: "Golondrina".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Inherits,
+                literal: "inherits".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Identifier,
+                literal: "Ave".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Mixed,
+                literal: "mixed".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::With,
+                literal: "with".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Identifier,
+                literal: "Volador".to_string(),
+                ..Default::default()
             },
             Token {
                 kind: TokenKind::SemiColon,
                 literal: ";".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Mixin,
+                literal: "mixin".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::New,
+                literal: "new".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::EOF,
+                literal: "".to_string(),
+                ..Default::default()
             },
         ];
 
@@ -521,6 +872,360 @@ mod test {
         exec_assert(expected, &mut lexer);
     }
 
+    #[test]
+    fn test_collection_literals() {
+        let input = "[1]#{2}";
+        let expected: Vec<Token> = vec![
+            Token {
+                kind: TokenKind::LeftBracket,
+                literal: "[".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Number,
+                literal: "1".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::RightBracket,
+                literal: "]".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Hash,
+                literal: "#".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::LeftBrace,
+                literal: "{".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Number,
+                literal: "2".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::RightBrace,
+                literal: "}".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::EOF,
+                literal: "".to_string(),
+                ..Default::default()
+            },
+        ];
+
+        let mut lexer = Lexer::new(input);
+        exec_assert(expected, &mut lexer);
+    }
+
+    #[test]
+    fn test_strings() {
+        let input = r#""hola" 'mundo' "dijo \"hola\"\n" "sin cerrar"#;
+        let expected: Vec<Token> = vec![
+            Token {
+                kind: TokenKind::String,
+                literal: "hola".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::String,
+                literal: "mundo".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::String,
+                literal: "dijo \"hola\"\n".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Error,
+                literal: "sin cerrar".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::EOF,
+                literal: "".to_string(),
+                ..Default::default()
+            },
+        ];
+
+        let mut lexer = Lexer::new(input);
+        exec_assert(expected, &mut lexer);
+    }
+
+    #[test]
+    fn test_ranges_and_decimals() {
+        let input = "1..10 3.14 1.even() n..m";
+        let expected: Vec<Token> = vec![
+            Token {
+                kind: TokenKind::Number,
+                literal: "1".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::DotDot,
+                literal: "..".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Number,
+                literal: "10".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Number,
+                literal: "3.14".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Number,
+                literal: "1".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Dot,
+                literal: ".".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Identifier,
+                literal: "even".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::LeftParen,
+                literal: "(".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::RightParen,
+                literal: ")".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Identifier,
+                literal: "n".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::DotDot,
+                literal: "..".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Identifier,
+                literal: "m".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::EOF,
+                literal: "".to_string(),
+                ..Default::default()
+            },
+        ];
+
+        let mut lexer = Lexer::new(input);
+        exec_assert(expected, &mut lexer);
+    }
+
+    #[test]
+    fn test_arrow() {
+        let input = "a->b - > -1";
+        let expected: Vec<Token> = vec![
+            Token {
+                kind: TokenKind::Identifier,
+                literal: "a".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Arrow,
+                literal: "->".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Identifier,
+                literal: "b".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Minus,
+                literal: "-".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::GreaterThan,
+                literal: ">".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Minus,
+                literal: "-".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Number,
+                literal: "1".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::EOF,
+                literal: "".to_string(),
+                ..Default::default()
+            },
+        ];
+
+        let mut lexer = Lexer::new(input);
+        exec_assert(expected, &mut lexer);
+    }
+
+    #[test]
+    fn test_operators() {
+        let input = "a >= 1 <= 2 && b || c % 2 and d or not e1 == null =>";
+        let expected: Vec<Token> = vec![
+            Token {
+                kind: TokenKind::Identifier,
+                literal: "a".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::GreaterEq,
+                literal: ">=".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Number,
+                literal: "1".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::LessEq,
+                literal: "<=".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Number,
+                literal: "2".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::And,
+                literal: "&&".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Identifier,
+                literal: "b".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Or,
+                literal: "||".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Identifier,
+                literal: "c".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Modulo,
+                literal: "%".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Number,
+                literal: "2".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::And,
+                literal: "and".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Identifier,
+                literal: "d".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Or,
+                literal: "or".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Not,
+                literal: "not".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Identifier,
+                literal: "e1".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Eq,
+                literal: "==".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::Null,
+                literal: "null".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::FatArrow,
+                literal: "=>".to_string(),
+                ..Default::default()
+            },
+            Token {
+                kind: TokenKind::EOF,
+                literal: "".to_string(),
+                ..Default::default()
+            },
+        ];
+
+        let mut lexer = Lexer::new(input);
+        exec_assert(expected, &mut lexer);
+    }
+
+    #[test]
+    fn test_spans() {
+        let input = "var x = 10\nobject pepita {\n  method volar() = \"hi\"\n}";
+        let expected = vec![
+            ("var", (1, 1), (1, 4)),
+            ("x", (1, 5), (1, 6)),
+            ("=", (1, 7), (1, 8)),
+            ("10", (1, 9), (1, 11)),
+            ("object", (2, 1), (2, 7)),
+            ("pepita", (2, 8), (2, 14)),
+            ("{", (2, 15), (2, 16)),
+            ("method", (3, 3), (3, 9)),
+            ("volar", (3, 10), (3, 15)),
+            ("(", (3, 15), (3, 16)),
+            (")", (3, 16), (3, 17)),
+            ("=", (3, 18), (3, 19)),
+            ("hi", (3, 20), (3, 24)),
+            ("}", (4, 1), (4, 2)),
+            ("", (4, 2), (4, 2)),
+        ];
+
+        let mut lexer = Lexer::new(input);
+        for (literal, start, end) in expected {
+            let token = lexer.next_token();
+            let span = (
+                (token.span.start.line, token.span.start.column),
+                (token.span.end.line, token.span.end.column),
+            );
+
+            assert_eq!(token.literal, literal);
+            assert_eq!(span, (start, end), "wrong span for {}", literal);
+        }
+    }
+
     fn exec_assert(expected: Vec<Token>, lexer: &mut Lexer) {
         for (index, exp_token) in expected.into_iter().enumerate() {
             let receive_token = lexer.next_token();
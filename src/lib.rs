@@ -0,0 +1,11 @@
+pub mod ast;
+pub mod cli;
+pub mod evaluator;
+mod lexer;
+pub mod lexer2;
+pub mod parser;
+pub mod repl;
+pub mod runner;
+pub mod runtime;
+pub mod token;
+pub mod validator;
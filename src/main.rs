@@ -1,15 +1,17 @@
-use crate::repl::start;
-use std::io;
+use rollok::cli;
+use std::process::ExitCode;
+use std::{env, io, thread};
 
-mod ast;
-mod lexer;
-mod lexer2;
-mod parser;
-mod repl;
-mod token;
+/// Deep Wollok recursion needs more room than the default main thread has.
+const STACK_SIZE: usize = 64 * 1024 * 1024;
 
-fn main() {
-    println!("Hello! This is the Rollok Programming Language!");
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
 
-    start(io::stdin(), io::stdout());
+    let handle = thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(move || cli::run(&args, &mut io::stdout(), &mut io::stderr()))
+        .expect("should have spawned the interpreter thread");
+
+    ExitCode::from(handle.join().unwrap_or(cli::EXIT_FAILURE))
 }
@@ -1,6 +1,11 @@
 use crate::ast::{
-    ExpressionNode, ExpressionStatement, Identifier, IntegerLiteral, Program, ReturnStatement,
-    StatementNode, VarStatement,
+    AssignStatement, BlockStatement, BooleanLiteral, CatchClause, ClassDeclaration, ClosureLiteral,
+    DecimalLiteral, DescribeBlock, ExpressionNode, ExpressionStatement, Identifier, IfExpression,
+    Import, InfixExpression, IntegerLiteral, ListLiteral, MessageSend, MethodBody,
+    MethodDeclaration, MixinDeclaration, NamedArgument, NewExpression, NullLiteral,
+    ObjectDeclaration, ObjectLiteral, Package, PrefixExpression, Program, ProgramDeclaration,
+    QualifiedName, RangeLiteral, ReturnStatement, SelfExpression, SetLiteral, StatementNode,
+    StringLiteral, SuperCall, TestDeclaration, ThrowStatement, TryExpression, VarStatement,
 };
 use crate::lexer2::Lexer;
 use crate::token::{Token, TokenKind};
@@ -9,17 +14,39 @@ use std::collections::HashMap;
 type PrefixParseFn = fn(parser: &mut Parser) -> Option<ExpressionNode>;
 type InfixParseFn = fn(parser: &mut Parser, exp: ExpressionNode) -> Option<ExpressionNode>;
 
+#[derive(PartialEq, PartialOrd, Clone, Copy)]
 enum PrecedenceLevel {
     Lowest = 0,
-    Equals = 1,
-    LessGreater = 2,
-    Sum = 3,
-    Product = 4,
-    Prefix = 5,
-    Call = 6,
+    Or = 1,
+    And = 2,
+    Equals = 3,
+    LessGreater = 4,
+    Pair = 5,
+    Range = 6,
+    Sum = 7,
+    Product = 8,
+    Prefix = 9,
+    Call = 10,
 }
 
-struct Parser {
+fn precedence_map(token_kind: &TokenKind) -> PrecedenceLevel {
+    match token_kind {
+        TokenKind::Or => PrecedenceLevel::Or,
+        TokenKind::And => PrecedenceLevel::And,
+        TokenKind::Eq | TokenKind::NotEq => PrecedenceLevel::Equals,
+        TokenKind::LessThan | TokenKind::GreaterThan | TokenKind::LessEq | TokenKind::GreaterEq => {
+            PrecedenceLevel::LessGreater
+        }
+        TokenKind::Arrow => PrecedenceLevel::Pair,
+        TokenKind::DotDot => PrecedenceLevel::Range,
+        TokenKind::Plus | TokenKind::Minus => PrecedenceLevel::Sum,
+        TokenKind::Multiply | TokenKind::Divide | TokenKind::Modulo => PrecedenceLevel::Product,
+        TokenKind::Dot => PrecedenceLevel::Call,
+        _ => PrecedenceLevel::Lowest,
+    }
+}
+
+pub struct Parser {
     lexer: Lexer,
     current_token: Token,
     peek_token: Token,
@@ -40,20 +67,77 @@ impl Parser {
         };
 
         parser.register_prefix(TokenKind::Identifier, Self::parse_identifier);
-        parser.register_prefix(TokenKind::Number, Self::parse_integer_literal);
+        parser.register_prefix(TokenKind::Number, Self::parse_number_literal);
+        parser.register_prefix(TokenKind::String, Self::parse_string_literal);
+        parser.register_prefix(TokenKind::True, Self::parse_boolean);
+        parser.register_prefix(TokenKind::False, Self::parse_boolean);
+        parser.register_prefix(TokenKind::Null, Self::parse_null);
+        parser.register_prefix(TokenKind::Bang, Self::parse_prefix_expression);
+        parser.register_prefix(TokenKind::Not, Self::parse_prefix_expression);
+        parser.register_prefix(TokenKind::Minus, Self::parse_prefix_expression);
+        parser.register_prefix(TokenKind::LeftParen, Self::parse_grouped_expression);
+        parser.register_prefix(TokenKind::Self_, Self::parse_self_expression);
+        parser.register_prefix(TokenKind::Super, Self::parse_super_call);
+        parser.register_prefix(TokenKind::Object, Self::parse_object_literal);
+        parser.register_prefix(TokenKind::New, Self::parse_new_expression);
+        // `[` only starts list literals; indexing would be an infix registration.
+        parser.register_prefix(TokenKind::LeftBracket, Self::parse_list_literal);
+        parser.register_prefix(TokenKind::Hash, Self::parse_set_literal);
+        parser.register_prefix(TokenKind::Try, Self::parse_try_expression);
+        parser.register_prefix(TokenKind::If, Self::parse_if_expression);
+        parser.register_prefix(TokenKind::LeftBrace, Self::parse_closure_literal);
+
+        parser.register_infix(TokenKind::Plus, Self::parse_infix_expression);
+        parser.register_infix(TokenKind::Minus, Self::parse_infix_expression);
+        parser.register_infix(TokenKind::Multiply, Self::parse_infix_expression);
+        parser.register_infix(TokenKind::Divide, Self::parse_infix_expression);
+        parser.register_infix(TokenKind::Modulo, Self::parse_infix_expression);
+        parser.register_infix(TokenKind::Eq, Self::parse_infix_expression);
+        parser.register_infix(TokenKind::NotEq, Self::parse_infix_expression);
+        parser.register_infix(TokenKind::LessThan, Self::parse_infix_expression);
+        parser.register_infix(TokenKind::GreaterThan, Self::parse_infix_expression);
+        parser.register_infix(TokenKind::LessEq, Self::parse_infix_expression);
+        parser.register_infix(TokenKind::GreaterEq, Self::parse_infix_expression);
+        parser.register_infix(TokenKind::And, Self::parse_infix_expression);
+        parser.register_infix(TokenKind::Or, Self::parse_infix_expression);
+        parser.register_infix(TokenKind::Dot, Self::parse_message_send);
+        parser.register_infix(TokenKind::DotDot, Self::parse_range_literal);
+        parser.register_infix(TokenKind::Arrow, Self::parse_infix_expression);
 
         parser.next_token();
         parser.next_token();
         parser
     }
 
+    fn parse_number_literal(&mut self) -> Option<ExpressionNode> {
+        if self.current_token.literal.contains('.') {
+            self.parse_decimal_literal()
+        } else {
+            self.parse_integer_literal()
+        }
+    }
+
+    fn parse_decimal_literal(&mut self) -> Option<ExpressionNode> {
+        match self.current_token.literal.parse::<f64>() {
+            Ok(value) => Some(ExpressionNode::Decimal(DecimalLiteral {
+                token: self.current_token.clone(),
+                value,
+            })),
+            Err(_) => {
+                let msg = format!("could not parse {} as decimal", self.current_token.literal);
+                self.errors.push(msg);
+                None
+            }
+        }
+    }
+
     fn parse_integer_literal(&mut self) -> Option<ExpressionNode> {
         let mut literal = IntegerLiteral {
             token: self.current_token.clone(),
             value: Default::default(),
         };
 
-        return match self.current_token.literal.parse::<i64>() {
+        match self.current_token.literal.parse::<i64>() {
             Ok(value) => {
                 literal.value = value;
                 Some(ExpressionNode::Integer(literal))
@@ -63,7 +147,14 @@ impl Parser {
                 self.errors.push(msg);
                 None
             }
-        };
+        }
+    }
+
+    fn parse_string_literal(&mut self) -> Option<ExpressionNode> {
+        Some(ExpressionNode::String(StringLiteral {
+            token: self.current_token.clone(),
+            value: self.current_token.literal.clone(),
+        }))
     }
 
     fn parse_identifier(&mut self) -> Option<ExpressionNode> {
@@ -73,298 +164,2131 @@ impl Parser {
         }))
     }
 
-    fn next_token(&mut self) {
-        self.current_token = self.peek_token.clone();
-        self.peek_token = self.lexer.next_token();
+    fn parse_boolean(&mut self) -> Option<ExpressionNode> {
+        Some(ExpressionNode::Boolean(BooleanLiteral {
+            token: self.current_token.clone(),
+            value: self.current_token_is(TokenKind::True),
+        }))
+    }
+
+    fn parse_null(&mut self) -> Option<ExpressionNode> {
+        Some(ExpressionNode::Null(NullLiteral {
+            token: self.current_token.clone(),
+        }))
     }
 
-    fn parse_program(&mut self) -> Option<Program> {
-        let mut program = Program { statements: vec![] };
+    fn parse_prefix_expression(&mut self) -> Option<ExpressionNode> {
+        let token = self.current_token.clone();
+        let operator = self.current_token.literal.clone();
 
-        while !self.current_token_is(TokenKind::EOF) {
-            if let Some(statement) = self.parse_statement() {
-                program.statements.push(statement);
-            }
-            self.next_token();
-        }
+        self.next_token();
 
-        Some(program)
-    }
+        let right = self.parse_expression(PrecedenceLevel::Prefix)?;
 
-    fn parse_statement(&mut self) -> Option<StatementNode> {
-        match self.current_token.kind {
-            TokenKind::Var => self.parse_var_statement(),
-            TokenKind::Return => self.parse_return_statement(),
-            _ => self.parse_expression_statement(),
-        }
+        Some(ExpressionNode::Prefix(PrefixExpression {
+            token,
+            operator,
+            right: Box::new(right),
+        }))
     }
 
-    fn parse_expression_statement(&mut self) -> Option<StatementNode> {
-        let stmt = ExpressionStatement {
-            token: self.current_token.clone(),
-            expression: self.parse_expression(PrecedenceLevel::Lowest),
-        };
+    fn parse_infix_expression(&mut self, left: ExpressionNode) -> Option<ExpressionNode> {
+        let token = self.current_token.clone();
+        let operator = self.current_token.literal.clone();
+        let precedence = self.current_precedence();
 
-        if self.peek_token_is(TokenKind::SemiColon) {
-            self.next_token();
-        }
+        self.next_token();
 
-        Some(StatementNode::Expression(stmt))
+        let right = self.parse_expression(precedence)?;
+
+        Some(ExpressionNode::Infix(InfixExpression {
+            token,
+            left: Box::new(left),
+            operator,
+            right: Box::new(right),
+        }))
     }
 
-    fn parse_expression(&mut self, precedence: PrecedenceLevel) -> Option<ExpressionNode> {
-        let prefix = self.prefix_parse_fns.get(&self.current_token.kind);
-        if let Some(prefix_fn) = prefix {
-            let left_exp = prefix_fn(self);
+    fn parse_range_literal(&mut self, start: ExpressionNode) -> Option<ExpressionNode> {
+        let token = self.current_token.clone();
+        let precedence = self.current_precedence();
 
-            return left_exp;
-        }
-        None
+        self.next_token();
+
+        let end = self.parse_expression(precedence)?;
+
+        Some(ExpressionNode::Range(RangeLiteral {
+            token,
+            start: Box::new(start),
+            end: Box::new(end),
+        }))
     }
 
-    fn parse_return_statement(&mut self) -> Option<StatementNode> {
-        let stmt = ReturnStatement {
-            token: self.current_token.clone(),
-            ret_value: Default::default(),
-        };
+    fn parse_grouped_expression(&mut self) -> Option<ExpressionNode> {
         self.next_token();
 
-        while !self.current_token_is(TokenKind::SemiColon) {
-            self.next_token();
+        let expression = self.parse_expression(PrecedenceLevel::Lowest);
+
+        if !self.expect_peek(TokenKind::RightParen) {
+            return None;
         }
 
-        Some(StatementNode::Return(stmt))
+        expression
     }
 
-    fn parse_var_statement(&mut self) -> Option<StatementNode> {
-        let mut stmt = VarStatement {
+    fn parse_self_expression(&mut self) -> Option<ExpressionNode> {
+        Some(ExpressionNode::SelfNode(SelfExpression {
             token: self.current_token.clone(),
-            name: Default::default(),
-            value: Default::default(),
-        };
+        }))
+    }
 
-        return if !self.expect_peek(TokenKind::Identifier) {
-            None
-        } else {
-            stmt.name = Identifier {
-                token: self.current_token.clone(),
-                value: self.current_token.literal.clone(),
-            };
+    fn parse_super_call(&mut self) -> Option<ExpressionNode> {
+        let token = self.current_token.clone();
 
-            if !self.expect_peek(TokenKind::Assign) {
-                None
-            } else {
-                self.next_token();
-                // TODO: need to parse expression
-                while self.peek_token_is(TokenKind::SemiColon) {
-                    self.next_token();
-                }
-                Some(StatementNode::Var(stmt))
-            }
-        };
+        if !self.expect_peek(TokenKind::LeftParen) {
+            return None;
+        }
+
+        let arguments = self.parse_expression_list(TokenKind::RightParen, false)?;
+
+        Some(ExpressionNode::Super(SuperCall { token, arguments }))
     }
 
-    fn expect_peek(&mut self, token_kind: TokenKind) -> bool {
-        if self.peek_token_is(token_kind) {
+    fn parse_message_send(&mut self, receiver: ExpressionNode) -> Option<ExpressionNode> {
+        let token = self.current_token.clone();
+
+        // `and`, `or` and `not` are keywords, but Boolean also understands them as messages
+        let keyword_message = matches!(
+            self.peek_token.kind,
+            TokenKind::And | TokenKind::Or | TokenKind::Not
+        ) && self.peek_token.literal.chars().all(char::is_alphabetic);
+
+        if keyword_message {
             self.next_token();
-            return true;
+        } else if !self.expect_peek(TokenKind::Identifier) {
+            return None;
         }
-        self.peek_error(token_kind);
-        false
-    }
 
-    fn peek_token_is(&self, token_kind: TokenKind) -> bool {
-        self.peek_token.kind == token_kind
-    }
+        let message = Identifier {
+            token: self.current_token.clone(),
+            value: self.current_token.literal.clone(),
+        };
 
-    fn current_token_is(&self, token_kind: TokenKind) -> bool {
-        self.current_token.kind == token_kind
-    }
+        if !self.expect_peek(TokenKind::LeftParen) {
+            return None;
+        }
 
-    fn errors(&self) -> &Vec<String> {
-        &self.errors
+        let arguments = self.parse_expression_list(TokenKind::RightParen, false)?;
+
+        Some(ExpressionNode::Send(MessageSend {
+            token,
+            receiver: Box::new(receiver),
+            message,
+            arguments,
+        }))
     }
 
-    fn peek_error(&mut self, token_kind: TokenKind) {
-        let msg = format!(
-            "expected next token to  be {}, got {} instead",
-            token_kind, self.peek_token.kind
-        );
+    fn parse_list_literal(&mut self) -> Option<ExpressionNode> {
+        let token = self.current_token.clone();
+        let elements = self.parse_expression_list(TokenKind::RightBracket, true)?;
 
-        self.errors.push(msg)
+        Some(ExpressionNode::List(ListLiteral { token, elements }))
     }
 
-    fn register_prefix(&mut self, token_kind: TokenKind, prefix_fn: PrefixParseFn) {
-        self.prefix_parse_fns.insert(token_kind, prefix_fn);
-    }
+    fn parse_set_literal(&mut self) -> Option<ExpressionNode> {
+        let token = self.current_token.clone();
 
-    fn register_infix(&mut self, token_kind: TokenKind, infix_fn: InfixParseFn) {
-        self.infix_parse_fns.insert(token_kind, infix_fn);
+        if !self.expect_peek(TokenKind::LeftBrace) {
+            return None;
+        }
+
+        let elements = self.parse_expression_list(TokenKind::RightBrace, true)?;
+
+        Some(ExpressionNode::Set(SetLiteral { token, elements }))
     }
-}
 
-#[cfg(test)]
-mod test {
-    use crate::ast::{ExpressionNode, Node, StatementNode};
-    use crate::lexer2::Lexer;
-    use crate::parser::Parser;
+    fn parse_if_expression(&mut self) -> Option<ExpressionNode> {
+        let token = self.current_token.clone();
 
-    #[test]
-    fn test_var_statements() {
-        let input = r#"
-            var x = 4;
-            var y = 10;
-            var foobar = 83838;
-        "#;
+        if !self.expect_peek(TokenKind::LeftParen) {
+            return None;
+        }
 
-        let lexer = Lexer::new(input);
-        let mut parser = Parser::new(lexer);
-        let program = parser.parse_program();
-        check_parser_errors(parser);
+        self.next_token();
+        let condition = self.parse_expression(PrecedenceLevel::Lowest)?;
 
-        match program {
-            Some(program) => {
-                assert_eq!(
-                    program.statements.len(),
-                    3,
-                    "statements does not contain 3 statements. got = {}",
-                    program.statements.len()
-                );
+        if !self.expect_peek(TokenKind::RightParen) {
+            return None;
+        }
 
-                let expected = vec!["x", "y", "foobar"];
+        self.next_token();
+        let consequence = self.parse_if_body()?;
 
-                for (idx, exp) in expected.into_iter().enumerate() {
-                    let stmt = &program.statements[idx];
-                    test_var_statement(stmt, exp);
-                }
-            }
-            None => panic!("parse program should not be none"),
+        let mut alternative = None;
+        if self.peek_token_is(TokenKind::Else) {
+            self.next_token();
+            self.next_token();
+            alternative = Some(self.parse_if_body()?);
         }
+
+        Some(ExpressionNode::If(IfExpression {
+            token,
+            condition: Box::new(condition),
+            consequence,
+            alternative,
+        }))
     }
 
-    #[test]
-    fn tests_return_statement() {
-        let input = r#"
-            return 5;
-            return 10;
-            return 156154;
-        "#;
+    fn parse_if_body(&mut self) -> Option<BlockStatement> {
+        if self.current_token_is(TokenKind::LeftBrace) {
+            return self.parse_block_statement();
+        }
 
-        let lexer = Lexer::new(input);
-        let mut parser = Parser::new(lexer);
-        let program = parser.parse_program();
-        check_parser_errors(parser);
+        let token = self.current_token.clone();
+        let statement = self.parse_statement()?;
 
-        match program {
-            Some(program) => {
-                assert_eq!(
-                    program.statements.len(),
-                    3,
-                    "statements does not contain 3 statements. got = {}",
-                    program.statements.len()
-                );
+        Some(BlockStatement {
+            token,
+            statements: vec![statement],
+        })
+    }
 
-                for stmt in program.statements {
-                    match stmt {
-                        StatementNode::Return(ret_stmt) => {
-                            assert_eq!(
-                                ret_stmt.token_literal(),
-                                "return",
-                                "token literal not `return`. got={:?}",
-                                ret_stmt.token_literal()
-                            )
-                        }
-                        other => panic!("stmt is not ReturnStatement. got={:?}", other),
-                    }
+    fn parse_closure_literal(&mut self) -> Option<ExpressionNode> {
+        let token = self.current_token.clone();
+        let mut parameters = vec![];
+
+        if self.closure_has_parameters() {
+            while !self.peek_token_is(TokenKind::FatArrow) {
+                self.next_token();
+                if self.current_token_is(TokenKind::Identifier) {
+                    parameters.push(Identifier {
+                        token: self.current_token.clone(),
+                        value: self.current_token.literal.clone(),
+                    });
                 }
             }
-            None => panic!("parse program should not be none"),
+            self.next_token();
         }
-    }
-
-    #[test]
-    fn test_identifier_expression() {
-        let input = "foobar;";
-
-        let lexer = Lexer::new(input);
-        let mut parser = Parser::new(lexer);
 
-        let program = parser.parse_program().unwrap();
-        check_parser_errors(parser);
+        let mut body = self.parse_block_statement()?;
+        body.token = token.clone();
 
-        assert_eq!(
-            program.statements.len(),
-            1,
-            "statements does not contain enough statements, got={}",
-            program.statements.len()
-        );
+        Some(ExpressionNode::Closure(ClosureLiteral {
+            token,
+            parameters,
+            body,
+        }))
+    }
 
-        match &program.statements[0] {
-            StatementNode::Expression(exp_stmt) => {
-                assert!(exp_stmt.expression.is_some());
+    // Looks past the `{` for `a, b =>` without consuming any token.
+    fn closure_has_parameters(&self) -> bool {
+        let mut lexer = self.lexer.clone();
+        let mut token = self.peek_token.clone();
+        let mut expect_identifier = true;
 
-                match exp_stmt.expression.as_ref().unwrap() {
-                    ExpressionNode::IdentifierNode(identifier) => {
-                        assert_eq!(
-                            identifier.value, "foobar",
-                            "identifier value not `foobar`. got={}",
-                            identifier.value
-                        );
-                        assert_eq!(
-                            identifier.token_literal(),
-                            "foobar",
-                            "identifier.token_literal() is not `foobar`. got={}",
-                            identifier.token_literal()
-                        )
-                    }
-                    other => panic!("expression not identifier. got={:?}", other),
-                }
+        loop {
+            match token.kind {
+                TokenKind::Identifier if expect_identifier => expect_identifier = false,
+                TokenKind::Comma if !expect_identifier => expect_identifier = true,
+                TokenKind::FatArrow => return !expect_identifier,
+                _ => return false,
             }
-            other => panic!(
-                "program.statements[0] is not ExpressionStatement. got={:?}",
-                other
-            ),
+            token = lexer.next_token();
         }
     }
 
-    #[test]
-    fn test_integer_literal_expression() {
-        let input = "5;";
+    fn parse_try_expression(&mut self) -> Option<ExpressionNode> {
+        let token = self.current_token.clone();
 
-        let lexer = Lexer::new(input);
-        let mut parser = Parser::new(lexer);
+        if !self.expect_peek(TokenKind::LeftBrace) {
+            return None;
+        }
 
-        let program = parser.parse_program().unwrap();
+        let body = self.parse_block_statement()?;
 
-        check_parser_errors(parser);
+        let mut catches = vec![];
+        while self.peek_token_is(TokenKind::Catch) {
+            self.next_token();
+            catches.push(self.parse_catch_clause()?);
+        }
 
-        assert_eq!(
+        let mut always = None;
+        if self.peek_token_is(TokenKind::Then) {
+            self.next_token();
+
+            if !self.expect_peek(TokenKind::Always) || !self.expect_peek(TokenKind::LeftBrace) {
+                return None;
+            }
+
+            always = Some(self.parse_block_statement()?);
+        }
+
+        Some(ExpressionNode::Try(TryExpression {
+            token,
+            body,
+            catches,
+            always,
+        }))
+    }
+
+    fn parse_catch_clause(&mut self) -> Option<CatchClause> {
+        let token = self.current_token.clone();
+
+        if !self.expect_peek(TokenKind::Identifier) {
+            return None;
+        }
+
+        let parameter = Identifier {
+            token: self.current_token.clone(),
+            value: self.current_token.literal.clone(),
+        };
+
+        let mut exception_type = None;
+        if self.peek_token_is(TokenKind::Colon) {
+            self.next_token();
+
+            if !self.expect_peek(TokenKind::Identifier) {
+                return None;
+            }
+
+            exception_type = Some(self.parse_qualified_name()?);
+        }
+
+        if !self.expect_peek(TokenKind::LeftBrace) {
+            return None;
+        }
+
+        let body = self.parse_block_statement()?;
+
+        Some(CatchClause {
+            token,
+            parameter,
+            exception_type,
+            body,
+        })
+    }
+
+    fn parse_expression_list(
+        &mut self,
+        end: TokenKind,
+        allow_trailing_comma: bool,
+    ) -> Option<Vec<ExpressionNode>> {
+        let mut list = vec![];
+
+        if self.peek_token_is(end) {
+            self.next_token();
+            return Some(list);
+        }
+
+        self.next_token();
+        list.push(self.parse_expression(PrecedenceLevel::Lowest)?);
+
+        while self.peek_token_is(TokenKind::Comma) {
+            self.next_token();
+            if allow_trailing_comma && self.peek_token_is(end) {
+                break;
+            }
+            self.next_token();
+            list.push(self.parse_expression(PrecedenceLevel::Lowest)?);
+        }
+
+        if !self.expect_peek(end) {
+            return None;
+        }
+
+        Some(list)
+    }
+
+    fn next_token(&mut self) {
+        self.current_token = self.peek_token.clone();
+        self.peek_token = self.lexer.next_token();
+    }
+
+    pub fn parse_program(&mut self) -> Option<Program> {
+        let mut program = Program {
+            imports: vec![],
+            statements: vec![],
+        };
+
+        while !self.current_token_is(TokenKind::EOF) {
+            if self.current_token_is(TokenKind::Import) {
+                if !program.statements.is_empty() {
+                    self.errors
+                        .push(String::from("imports must appear before any declaration"));
+                }
+                if let Some(import) = self.parse_import() {
+                    program.imports.push(import);
+                }
+            } else if let Some(statement) = self.parse_statement() {
+                program.statements.push(statement);
+            }
+            self.next_token();
+        }
+
+        Some(program)
+    }
+
+    fn parse_import(&mut self) -> Option<Import> {
+        let token = self.current_token.clone();
+
+        if !self.expect_peek(TokenKind::Identifier) {
+            return None;
+        }
+
+        let mut name = QualifiedName {
+            token: self.current_token.clone(),
+            parts: vec![Identifier {
+                token: self.current_token.clone(),
+                value: self.current_token.literal.clone(),
+            }],
+        };
+        let mut wildcard = false;
+
+        while self.peek_token_is(TokenKind::Dot) {
+            self.next_token();
+
+            if self.peek_token_is(TokenKind::Multiply) {
+                self.next_token();
+                wildcard = true;
+                break;
+            }
+
+            if !self.expect_peek(TokenKind::Identifier) {
+                return None;
+            }
+
+            name.parts.push(Identifier {
+                token: self.current_token.clone(),
+                value: self.current_token.literal.clone(),
+            });
+        }
+
+        if self.peek_token_is(TokenKind::SemiColon) {
+            self.next_token();
+        }
+
+        Some(Import {
+            token,
+            name,
+            wildcard,
+        })
+    }
+
+    fn parse_package(&mut self) -> Option<StatementNode> {
+        let token = self.current_token.clone();
+
+        if !self.expect_peek(TokenKind::Identifier) {
+            return None;
+        }
+
+        let name = self.parse_qualified_name()?;
+
+        if !self.expect_peek(TokenKind::LeftBrace) {
+            return None;
+        }
+
+        let mut statements = vec![];
+
+        self.next_token();
+
+        while !self.current_token_is(TokenKind::RightBrace) {
+            if self.current_token_is(TokenKind::EOF) {
+                self.errors
+                    .push(String::from("expected }, got EOF instead"));
+                return None;
+            }
+            if let Some(statement) = self.parse_statement() {
+                statements.push(statement);
+            }
+            self.next_token();
+        }
+
+        Some(StatementNode::Package(Package {
+            token,
+            name,
+            statements,
+        }))
+    }
+
+    fn parse_program_declaration(&mut self) -> Option<StatementNode> {
+        let token = self.current_token.clone();
+
+        if !self.expect_peek(TokenKind::Identifier) {
+            return None;
+        }
+
+        let name = Identifier {
+            token: self.current_token.clone(),
+            value: self.current_token.literal.clone(),
+        };
+
+        if !self.expect_peek(TokenKind::LeftBrace) {
+            return None;
+        }
+
+        let body = self.parse_block_statement()?;
+
+        Some(StatementNode::Program(ProgramDeclaration {
+            token,
+            name,
+            body,
+        }))
+    }
+
+    fn parse_describe_block(&mut self) -> Option<StatementNode> {
+        let token = self.current_token.clone();
+
+        if !self.expect_peek(TokenKind::String) {
+            return None;
+        }
+
+        let name = StringLiteral {
+            token: self.current_token.clone(),
+            value: self.current_token.literal.clone(),
+        };
+
+        if !self.expect_peek(TokenKind::LeftBrace) {
+            return None;
+        }
+
+        let mut describe = DescribeBlock {
+            token,
+            name,
+            fields: vec![],
+            methods: vec![],
+            tests: vec![],
+        };
+
+        self.next_token();
+
+        while !self.current_token_is(TokenKind::RightBrace) {
+            match self.current_token.kind {
+                TokenKind::Var | TokenKind::Const => {
+                    describe.fields.push(self.parse_var_declaration()?)
+                }
+                TokenKind::Method | TokenKind::Override => {
+                    describe.methods.push(self.parse_method_declaration()?)
+                }
+                TokenKind::Test | TokenKind::Only => {
+                    describe.tests.push(self.parse_test_declaration()?)
+                }
+                TokenKind::EOF => {
+                    self.errors
+                        .push(String::from("expected }, got EOF instead"));
+                    return None;
+                }
+                other => {
+                    let msg = format!("unexpected {} in describe body", other);
+                    self.errors.push(msg);
+                    return None;
+                }
+            }
+            self.next_token();
+        }
+
+        Some(StatementNode::Describe(describe))
+    }
+
+    fn parse_test_declaration(&mut self) -> Option<TestDeclaration> {
+        let only = self.current_token_is(TokenKind::Only);
+
+        if only && !self.expect_peek(TokenKind::Test) {
+            return None;
+        }
+
+        let token = self.current_token.clone();
+
+        if !self.expect_peek(TokenKind::String) {
+            return None;
+        }
+
+        let name = StringLiteral {
+            token: self.current_token.clone(),
+            value: self.current_token.literal.clone(),
+        };
+
+        if !self.expect_peek(TokenKind::LeftBrace) {
+            return None;
+        }
+
+        let body = self.parse_block_statement()?;
+
+        Some(TestDeclaration {
+            token,
+            name,
+            only,
+            body,
+        })
+    }
+
+    fn parse_statement(&mut self) -> Option<StatementNode> {
+        match self.current_token.kind {
+            TokenKind::Package => self.parse_package(),
+            TokenKind::Program => self.parse_program_declaration(),
+            TokenKind::Describe => self.parse_describe_block(),
+            TokenKind::Test | TokenKind::Only => {
+                self.parse_test_declaration().map(StatementNode::Test)
+            }
+            TokenKind::Var | TokenKind::Const => self.parse_var_statement(),
+            TokenKind::Identifier if self.peek_token_is(TokenKind::Assign) => {
+                self.parse_assign_statement()
+            }
+            TokenKind::Return => self.parse_return_statement(),
+            TokenKind::Throw => self.parse_throw_statement(),
+            TokenKind::Object if self.peek_token_is(TokenKind::Identifier) => {
+                self.parse_object_declaration()
+            }
+            TokenKind::Class => self.parse_class_declaration(),
+            TokenKind::Mixin => self.parse_mixin_declaration(),
+            _ => self.parse_expression_statement(),
+        }
+    }
+
+    fn parse_expression_statement(&mut self) -> Option<StatementNode> {
+        let stmt = ExpressionStatement {
+            token: self.current_token.clone(),
+            expression: self.parse_expression(PrecedenceLevel::Lowest),
+        };
+
+        if self.peek_token_is(TokenKind::SemiColon) {
+            self.next_token();
+        }
+
+        Some(StatementNode::Expression(stmt))
+    }
+
+    fn parse_expression(&mut self, precedence: PrecedenceLevel) -> Option<ExpressionNode> {
+        let prefix = match self.prefix_parse_fns.get(&self.current_token.kind) {
+            Some(prefix_fn) => *prefix_fn,
+            None => {
+                self.no_prefix_parse_fn_error(self.current_token.kind);
+                return None;
+            }
+        };
+
+        let mut left_exp = prefix(self)?;
+
+        while !self.peek_token_is(TokenKind::SemiColon) && precedence < self.peek_precedence() {
+            let infix = match self.infix_parse_fns.get(&self.peek_token.kind) {
+                Some(infix_fn) => *infix_fn,
+                None => return Some(left_exp),
+            };
+
+            self.next_token();
+            left_exp = infix(self, left_exp)?;
+        }
+
+        Some(left_exp)
+    }
+
+    fn parse_return_statement(&mut self) -> Option<StatementNode> {
+        let mut stmt = ReturnStatement {
+            token: self.current_token.clone(),
+            ret_value: Default::default(),
+        };
+
+        if !self.peek_token_is(TokenKind::SemiColon) && !self.peek_token_is(TokenKind::RightBrace) {
+            self.next_token();
+            stmt.ret_value = self.parse_expression(PrecedenceLevel::Lowest);
+        }
+
+        if self.peek_token_is(TokenKind::SemiColon) {
+            self.next_token();
+        }
+
+        Some(StatementNode::Return(stmt))
+    }
+
+    fn parse_throw_statement(&mut self) -> Option<StatementNode> {
+        let token = self.current_token.clone();
+
+        self.next_token();
+
+        let exception = self.parse_expression(PrecedenceLevel::Lowest)?;
+
+        if self.peek_token_is(TokenKind::SemiColon) {
+            self.next_token();
+        }
+
+        Some(StatementNode::Throw(ThrowStatement { token, exception }))
+    }
+
+    fn parse_assign_statement(&mut self) -> Option<StatementNode> {
+        let name = Identifier {
+            token: self.current_token.clone(),
+            value: self.current_token.literal.clone(),
+        };
+
+        self.next_token();
+        let token = self.current_token.clone();
+
+        self.next_token();
+        let value = self.parse_expression(PrecedenceLevel::Lowest)?;
+
+        if self.peek_token_is(TokenKind::SemiColon) {
+            self.next_token();
+        }
+
+        Some(StatementNode::Assign(AssignStatement {
+            token,
+            name,
+            value,
+        }))
+    }
+
+    fn parse_var_statement(&mut self) -> Option<StatementNode> {
+        self.parse_var_declaration().map(StatementNode::Var)
+    }
+
+    fn parse_var_declaration(&mut self) -> Option<VarStatement> {
+        let mut stmt = VarStatement {
+            token: self.current_token.clone(),
+            name: Default::default(),
+            value: Default::default(),
+        };
+
+        if !self.expect_peek(TokenKind::Identifier) {
+            return None;
+        }
+
+        stmt.name = Identifier {
+            token: self.current_token.clone(),
+            value: self.current_token.literal.clone(),
+        };
+
+        if self.peek_token_is(TokenKind::Assign) {
+            self.next_token();
+            self.next_token();
+            stmt.value = Some(self.parse_expression(PrecedenceLevel::Lowest)?);
+        }
+
+        if self.peek_token_is(TokenKind::SemiColon) {
+            self.next_token();
+        }
+
+        Some(stmt)
+    }
+
+    fn parse_object_declaration(&mut self) -> Option<StatementNode> {
+        let token = self.current_token.clone();
+
+        if !self.expect_peek(TokenKind::Identifier) {
+            return None;
+        }
+
+        let name = Identifier {
+            token: self.current_token.clone(),
+            value: self.current_token.literal.clone(),
+        };
+
+        let parent = self.parse_inherits()?;
+        let mixins = self.parse_mixins()?;
+
+        if !self.expect_peek(TokenKind::LeftBrace) {
+            return None;
+        }
+
+        let (fields, methods) = self.parse_object_members()?;
+
+        Some(StatementNode::Object(ObjectDeclaration {
+            token,
+            name,
+            parent,
+            mixins,
+            fields,
+            methods,
+        }))
+    }
+
+    fn parse_object_literal(&mut self) -> Option<ExpressionNode> {
+        let token = self.current_token.clone();
+
+        let parent = self.parse_inherits()?;
+        let mixins = self.parse_mixins()?;
+
+        if !self.expect_peek(TokenKind::LeftBrace) {
+            return None;
+        }
+
+        let (fields, methods) = self.parse_object_members()?;
+
+        Some(ExpressionNode::ObjectLiteral(ObjectLiteral {
+            token,
+            parent,
+            mixins,
+            fields,
+            methods,
+        }))
+    }
+
+    fn parse_class_declaration(&mut self) -> Option<StatementNode> {
+        let token = self.current_token.clone();
+
+        if !self.expect_peek(TokenKind::Identifier) {
+            return None;
+        }
+
+        let name = Identifier {
+            token: self.current_token.clone(),
+            value: self.current_token.literal.clone(),
+        };
+
+        let parent = self.parse_inherits()?;
+        let mixins = self.parse_mixins()?;
+
+        if !self.expect_peek(TokenKind::LeftBrace) {
+            return None;
+        }
+
+        let (fields, methods) = self.parse_object_members()?;
+
+        Some(StatementNode::Class(ClassDeclaration {
+            token,
+            name,
+            parent,
+            mixins,
+            fields,
+            methods,
+        }))
+    }
+
+    fn parse_mixin_declaration(&mut self) -> Option<StatementNode> {
+        let token = self.current_token.clone();
+
+        if !self.expect_peek(TokenKind::Identifier) {
+            return None;
+        }
+
+        let name = Identifier {
+            token: self.current_token.clone(),
+            value: self.current_token.literal.clone(),
+        };
+
+        let mixins = self.parse_mixins()?;
+
+        if !self.expect_peek(TokenKind::LeftBrace) {
+            return None;
+        }
+
+        let (fields, methods) = self.parse_object_members()?;
+
+        Some(StatementNode::Mixin(MixinDeclaration {
+            token,
+            name,
+            mixins,
+            fields,
+            methods,
+        }))
+    }
+
+    fn parse_new_expression(&mut self) -> Option<ExpressionNode> {
+        let token = self.current_token.clone();
+
+        if !self.expect_peek(TokenKind::Identifier) {
+            return None;
+        }
+
+        let class = self.parse_qualified_name()?;
+
+        if !self.expect_peek(TokenKind::LeftParen) {
+            return None;
+        }
+
+        let arguments = self.parse_named_arguments()?;
+
+        Some(ExpressionNode::New(NewExpression {
+            token,
+            class,
+            arguments,
+        }))
+    }
+
+    fn parse_named_arguments(&mut self) -> Option<Vec<NamedArgument>> {
+        let mut arguments = vec![];
+
+        if self.peek_token_is(TokenKind::RightParen) {
+            self.next_token();
+            return Some(arguments);
+        }
+
+        loop {
+            if !self.expect_peek(TokenKind::Identifier) {
+                return None;
+            }
+
+            let name = Identifier {
+                token: self.current_token.clone(),
+                value: self.current_token.literal.clone(),
+            };
+
+            if !self.expect_peek(TokenKind::Assign) {
+                return None;
+            }
+
+            self.next_token();
+            let value = self.parse_expression(PrecedenceLevel::Lowest)?;
+            arguments.push(NamedArgument { name, value });
+
+            if !self.peek_token_is(TokenKind::Comma) {
+                break;
+            }
+            self.next_token();
+        }
+
+        if !self.expect_peek(TokenKind::RightParen) {
+            return None;
+        }
+
+        Some(arguments)
+    }
+
+    fn parse_mixins(&mut self) -> Option<Vec<QualifiedName>> {
+        let mut mixins = vec![];
+
+        if !self.peek_token_is(TokenKind::Mixed) {
+            return Some(mixins);
+        }
+
+        self.next_token();
+
+        if !self.expect_peek(TokenKind::With) {
+            return None;
+        }
+
+        loop {
+            if !self.expect_peek(TokenKind::Identifier) {
+                return None;
+            }
+
+            mixins.push(self.parse_qualified_name()?);
+
+            if !self.peek_token_is(TokenKind::And) {
+                break;
+            }
+            self.next_token();
+        }
+
+        Some(mixins)
+    }
+
+    fn parse_inherits(&mut self) -> Option<Option<QualifiedName>> {
+        if !self.peek_token_is(TokenKind::Inherits) {
+            return Some(None);
+        }
+
+        self.next_token();
+
+        if !self.expect_peek(TokenKind::Identifier) {
+            return None;
+        }
+
+        Some(Some(self.parse_qualified_name()?))
+    }
+
+    fn parse_qualified_name(&mut self) -> Option<QualifiedName> {
+        let mut name = QualifiedName {
+            token: self.current_token.clone(),
+            parts: vec![Identifier {
+                token: self.current_token.clone(),
+                value: self.current_token.literal.clone(),
+            }],
+        };
+
+        while self.peek_token_is(TokenKind::Dot) {
+            self.next_token();
+
+            if !self.expect_peek(TokenKind::Identifier) {
+                return None;
+            }
+
+            name.parts.push(Identifier {
+                token: self.current_token.clone(),
+                value: self.current_token.literal.clone(),
+            });
+        }
+
+        Some(name)
+    }
+
+    fn parse_object_members(&mut self) -> Option<(Vec<VarStatement>, Vec<MethodDeclaration>)> {
+        let mut fields = vec![];
+        let mut methods = vec![];
+
+        self.next_token();
+
+        while !self.current_token_is(TokenKind::RightBrace) {
+            match self.current_token.kind {
+                TokenKind::Var | TokenKind::Const => fields.push(self.parse_var_declaration()?),
+                TokenKind::Method | TokenKind::Override => {
+                    methods.push(self.parse_method_declaration()?)
+                }
+                TokenKind::EOF => {
+                    self.errors
+                        .push(String::from("expected }, got EOF instead"));
+                    return None;
+                }
+                other => {
+                    let msg = format!("unexpected {} in object body", other);
+                    self.errors.push(msg);
+                    return None;
+                }
+            }
+            self.next_token();
+        }
+
+        Some((fields, methods))
+    }
+
+    fn parse_method_declaration(&mut self) -> Option<MethodDeclaration> {
+        let overrides = self.current_token_is(TokenKind::Override);
+
+        if overrides && !self.expect_peek(TokenKind::Method) {
+            return None;
+        }
+
+        let token = self.current_token.clone();
+
+        if !self.expect_peek(TokenKind::Identifier) {
+            return None;
+        }
+
+        let name = Identifier {
+            token: self.current_token.clone(),
+            value: self.current_token.literal.clone(),
+        };
+
+        if !self.expect_peek(TokenKind::LeftParen) {
+            return None;
+        }
+
+        let parameters = self.parse_parameters()?;
+
+        let body = if self.peek_token_is(TokenKind::Assign) {
+            self.next_token();
+            self.next_token();
+            MethodBody::Expression(self.parse_expression(PrecedenceLevel::Lowest)?)
+        } else if self.peek_token_is(TokenKind::LeftBrace) {
+            self.next_token();
+            MethodBody::Block(self.parse_block_statement()?)
+        } else {
+            MethodBody::Abstract
+        };
+
+        Some(MethodDeclaration {
+            token,
+            name,
+            parameters,
+            body,
+            overrides,
+        })
+    }
+
+    fn parse_parameters(&mut self) -> Option<Vec<Identifier>> {
+        let mut identifiers = vec![];
+
+        if self.peek_token_is(TokenKind::RightParen) {
+            self.next_token();
+            return Some(identifiers);
+        }
+
+        if !self.expect_peek(TokenKind::Identifier) {
+            return None;
+        }
+        identifiers.push(Identifier {
+            token: self.current_token.clone(),
+            value: self.current_token.literal.clone(),
+        });
+
+        while self.peek_token_is(TokenKind::Comma) {
+            self.next_token();
+            if !self.expect_peek(TokenKind::Identifier) {
+                return None;
+            }
+            identifiers.push(Identifier {
+                token: self.current_token.clone(),
+                value: self.current_token.literal.clone(),
+            });
+        }
+
+        if !self.expect_peek(TokenKind::RightParen) {
+            return None;
+        }
+
+        Some(identifiers)
+    }
+
+    fn parse_block_statement(&mut self) -> Option<BlockStatement> {
+        let mut block = BlockStatement {
+            token: self.current_token.clone(),
+            statements: vec![],
+        };
+
+        self.next_token();
+
+        while !self.current_token_is(TokenKind::RightBrace) {
+            if self.current_token_is(TokenKind::EOF) {
+                self.errors
+                    .push(String::from("expected }, got EOF instead"));
+                return None;
+            }
+            if let Some(statement) = self.parse_statement() {
+                block.statements.push(statement);
+            }
+            self.next_token();
+        }
+
+        Some(block)
+    }
+
+    fn expect_peek(&mut self, token_kind: TokenKind) -> bool {
+        if self.peek_token_is(token_kind) {
+            self.next_token();
+            return true;
+        }
+        self.peek_error(token_kind);
+        false
+    }
+
+    fn peek_token_is(&self, token_kind: TokenKind) -> bool {
+        self.peek_token.kind == token_kind
+    }
+
+    fn current_token_is(&self, token_kind: TokenKind) -> bool {
+        self.current_token.kind == token_kind
+    }
+
+    fn peek_precedence(&self) -> PrecedenceLevel {
+        precedence_map(&self.peek_token.kind)
+    }
+
+    fn current_precedence(&self) -> PrecedenceLevel {
+        precedence_map(&self.current_token.kind)
+    }
+
+    pub fn errors(&self) -> &Vec<String> {
+        &self.errors
+    }
+
+    fn peek_error(&mut self, token_kind: TokenKind) {
+        let msg = format!(
+            "expected next token to  be {}, got {} instead",
+            token_kind, self.peek_token.kind
+        );
+
+        self.errors.push(msg)
+    }
+
+    fn no_prefix_parse_fn_error(&mut self, token_kind: TokenKind) {
+        let msg = format!("no prefix parse function for {} found", token_kind);
+        self.errors.push(msg);
+    }
+
+    fn register_prefix(&mut self, token_kind: TokenKind, prefix_fn: PrefixParseFn) {
+        self.prefix_parse_fns.insert(token_kind, prefix_fn);
+    }
+
+    fn register_infix(&mut self, token_kind: TokenKind, infix_fn: InfixParseFn) {
+        self.infix_parse_fns.insert(token_kind, infix_fn);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ast::{ExpressionNode, MethodBody, Node, StatementNode};
+    use crate::lexer2::Lexer;
+    use crate::parser::Parser;
+
+    #[test]
+    fn test_var_statements() {
+        let input = r#"
+            var x = 4;
+            var y = 10;
+            var foobar = 83838;
+        "#;
+
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program();
+        check_parser_errors(parser);
+
+        match program {
+            Some(program) => {
+                assert_eq!(
+                    program.statements.len(),
+                    3,
+                    "statements does not contain 3 statements. got = {}",
+                    program.statements.len()
+                );
+
+                let expected = vec!["x", "y", "foobar"];
+
+                for (idx, exp) in expected.into_iter().enumerate() {
+                    let stmt = &program.statements[idx];
+                    test_var_statement(stmt, exp);
+                }
+            }
+            None => panic!("parse program should not be none"),
+        }
+    }
+
+    #[test]
+    fn tests_return_statement() {
+        let input = r#"
+            return 5;
+            return 10;
+            return 156154;
+        "#;
+
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program();
+        check_parser_errors(parser);
+
+        match program {
+            Some(program) => {
+                assert_eq!(
+                    program.statements.len(),
+                    3,
+                    "statements does not contain 3 statements. got = {}",
+                    program.statements.len()
+                );
+
+                for stmt in program.statements {
+                    match stmt {
+                        StatementNode::Return(ret_stmt) => {
+                            assert_eq!(
+                                ret_stmt.token_literal(),
+                                "return",
+                                "token literal not `return`. got={:?}",
+                                ret_stmt.token_literal()
+                            )
+                        }
+                        other => panic!("stmt is not ReturnStatement. got={:?}", other),
+                    }
+                }
+            }
+            None => panic!("parse program should not be none"),
+        }
+    }
+
+    #[test]
+    fn test_identifier_expression() {
+        let input = "foobar;";
+
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+
+        let program = parser.parse_program().unwrap();
+        check_parser_errors(parser);
+
+        assert_eq!(
+            program.statements.len(),
+            1,
+            "statements does not contain enough statements, got={}",
+            program.statements.len()
+        );
+
+        match &program.statements[0] {
+            StatementNode::Expression(exp_stmt) => {
+                assert!(exp_stmt.expression.is_some());
+
+                match exp_stmt.expression.as_ref().unwrap() {
+                    ExpressionNode::IdentifierNode(identifier) => {
+                        assert_eq!(
+                            identifier.value, "foobar",
+                            "identifier value not `foobar`. got={}",
+                            identifier.value
+                        );
+                        assert_eq!(
+                            identifier.token_literal(),
+                            "foobar",
+                            "identifier.token_literal() is not `foobar`. got={}",
+                            identifier.token_literal()
+                        )
+                    }
+                    other => panic!("expression not identifier. got={:?}", other),
+                }
+            }
+            other => panic!(
+                "program.statements[0] is not ExpressionStatement. got={:?}",
+                other
+            ),
+        }
+    }
+
+    #[test]
+    fn test_integer_literal_expression() {
+        let input = "5;";
+
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+
+        let program = parser.parse_program().unwrap();
+
+        check_parser_errors(parser);
+
+        assert_eq!(
+            program.statements.len(),
+            1,
+            "program.statement does not contain enough statements. got={}",
+            program.statements.len()
+        );
+
+        match &program.statements[0] {
+            StatementNode::Expression(exp_stmt) => {
+                assert!(exp_stmt.expression.is_some());
+                match exp_stmt.expression.as_ref().unwrap() {
+                    ExpressionNode::Integer(integer) => {
+                        assert_eq!(
+                            integer.value, 5,
+                            "integer.value not `5`. got={}",
+                            integer.value
+                        );
+                        assert_eq!(
+                            integer.token_literal(),
+                            "5",
+                            "integer.value not `5`. got={}",
+                            integer.token_literal()
+                        );
+                    }
+                    other => panic!("Expression not an IntegerLiteral. got={:?}", other),
+                }
+            }
+            other => panic!(
+                "program.statements[0] is not ExpressionStatement. got={:?}",
+                other
+            ),
+        }
+    }
+
+    #[test]
+    fn test_operator_precedence_parsing() {
+        let tests = vec![
+            ("-a * b", "((-a) * b)"),
+            ("!-a", "(!(-a))"),
+            ("a + b - c", "((a + b) - c)"),
+            ("a + b * c", "(a + (b * c))"),
+            ("5 > 4 == 3 < 4", "((5 > 4) == (3 < 4))"),
+            ("(5 + 5) * 2", "((5 + 5) * 2)"),
+            ("a.b() + c.d(1, 2)", "(a.b() + c.d(1, 2))"),
+            ("self.energia() * 2", "(self.energia() * 2)"),
+            ("pepita.volar(10).energia()", "pepita.volar(10).energia()"),
+            ("1..10", "(1..10)"),
+            ("1..n + 1", "(1..(n + 1))"),
+            ("a * 2..b - 1", "((a * 2)..(b - 1))"),
+            ("1..5 == r", "((1..5) == r)"),
+            ("(1..10).forEach(f)", "(1..10).forEach(f)"),
+            ("1..10.sum()", "(1..10.sum())"),
+            ("0.5 * 2", "(0.5 * 2)"),
+            (r#""uno" -> 1"#, r#"("uno" -> 1)"#),
+            ("a -> b + 1", "(a -> (b + 1))"),
+            ("a -> 1..3", "(a -> (1..3))"),
+            ("a -> b == c", "((a -> b) == c)"),
+            ("[1 -> 2, a->b]", "[(1 -> 2), (a -> b)]"),
+            ("true", "true"),
+            ("!false == null", "((!false) == null)"),
+            ("a || b && c", "(a || (b && c))"),
+            ("a or b and not c", "(a or (b and (not c)))"),
+            ("a >= 1 == b <= 2", "((a >= 1) == (b <= 2))"),
+            ("a % 2 + 1", "((a % 2) + 1)"),
+            ("{ x, y => x + y }", "{ x, y => (x + y) }"),
+            ("{ 1 }.apply()", "{ 1 }.apply()"),
+            ("{ }", "{ }"),
+            ("list.map({ x => x * 2 })", "list.map({ x => (x * 2) })"),
+            ("if (a > 1) b else c", "if ((a > 1)) { b } else { c }"),
+            (
+                "if (a) { return 1 } else if (b) 2 else 3",
+                "if (a) { return 1; } else { if (b) { 2 } else { 3 } }",
+            ),
+            ("x = x + 1", "x = (x + 1);"),
+            ("a.and(b).or(c)", "a.and(b).or(c)"),
+        ];
+
+        for (input, expected) in tests {
+            let lexer = Lexer::new(input);
+            let mut parser = Parser::new(lexer);
+            let program = parser.parse_program().unwrap();
+            check_parser_errors(parser);
+
+            assert_eq!(
+                program.print_string(),
+                expected,
+                "expected={}, got={}",
+                expected,
+                program.print_string()
+            );
+        }
+    }
+
+    #[test]
+    fn test_decimal_literal_expression() {
+        let input = "3.25;";
+
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program().unwrap();
+        check_parser_errors(parser);
+
+        match &program.statements[0] {
+            StatementNode::Expression(exp_stmt) => match exp_stmt.expression.as_ref().unwrap() {
+                ExpressionNode::Decimal(decimal) => {
+                    assert_eq!(
+                        decimal.value, 3.25,
+                        "decimal.value not `3.25`. got={}",
+                        decimal.value
+                    );
+                }
+                other => panic!("Expression not a DecimalLiteral. got={:?}", other),
+            },
+            other => panic!(
+                "program.statements[0] is not ExpressionStatement. got={:?}",
+                other
+            ),
+        }
+    }
+
+    #[test]
+    fn test_range_literal() {
+        let input = "1..n;";
+
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program().unwrap();
+        check_parser_errors(parser);
+
+        match &program.statements[0] {
+            StatementNode::Expression(exp_stmt) => match exp_stmt.expression.as_ref().unwrap() {
+                ExpressionNode::Range(range) => {
+                    assert!(matches!(*range.start, ExpressionNode::Integer(_)));
+                    assert!(matches!(*range.end, ExpressionNode::IdentifierNode(_)));
+                }
+                other => panic!("Expression not a RangeLiteral. got={:?}", other),
+            },
+            other => panic!(
+                "program.statements[0] is not ExpressionStatement. got={:?}",
+                other
+            ),
+        }
+    }
+
+    #[test]
+    fn test_closure_literal() {
+        let input = "{ a, b => var c = a + b  c * 2 }";
+
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program().unwrap();
+        check_parser_errors(parser);
+
+        match &program.statements[0] {
+            StatementNode::Expression(exp_stmt) => match exp_stmt.expression.as_ref().unwrap() {
+                ExpressionNode::Closure(closure) => {
+                    let parameters: Vec<&str> = closure
+                        .parameters
+                        .iter()
+                        .map(|parameter| parameter.value.as_str())
+                        .collect();
+                    assert_eq!(parameters, vec!["a", "b"]);
+                    assert_eq!(
+                        closure.body.statements.len(),
+                        2,
+                        "closure.body does not contain 2 statements. got={}",
+                        closure.body.statements.len()
+                    );
+                }
+                other => panic!("Expression not a ClosureLiteral. got={:?}", other),
+            },
+            other => panic!(
+                "program.statements[0] is not ExpressionStatement. got={:?}",
+                other
+            ),
+        }
+    }
+
+    #[test]
+    fn test_if_expression() {
+        let input = "if (x < y) { x } else { y; z }";
+
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program().unwrap();
+        check_parser_errors(parser);
+
+        match &program.statements[0] {
+            StatementNode::Expression(exp_stmt) => match exp_stmt.expression.as_ref().unwrap() {
+                ExpressionNode::If(if_exp) => {
+                    assert_eq!(if_exp.condition.print_string(), "(x < y)");
+                    assert_eq!(if_exp.consequence.statements.len(), 1);
+                    assert_eq!(if_exp.alternative.as_ref().unwrap().statements.len(), 2);
+                }
+                other => panic!("Expression not an IfExpression. got={:?}", other),
+            },
+            other => panic!(
+                "program.statements[0] is not ExpressionStatement. got={:?}",
+                other
+            ),
+        }
+    }
+
+    #[test]
+    fn test_self_expression() {
+        let input = "self;";
+
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program().unwrap();
+        check_parser_errors(parser);
+
+        match &program.statements[0] {
+            StatementNode::Expression(exp_stmt) => match exp_stmt.expression.as_ref().unwrap() {
+                ExpressionNode::SelfNode(self_exp) => {
+                    assert_eq!(
+                        self_exp.token_literal(),
+                        "self",
+                        "self_exp.token_literal() is not `self`. got={}",
+                        self_exp.token_literal()
+                    );
+                }
+                other => panic!("expression not SelfExpression. got={:?}", other),
+            },
+            other => panic!(
+                "program.statements[0] is not ExpressionStatement. got={:?}",
+                other
+            ),
+        }
+    }
+
+    #[test]
+    fn test_super_call() {
+        let input = "super(1, a + 2);";
+
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program().unwrap();
+        check_parser_errors(parser);
+
+        match &program.statements[0] {
+            StatementNode::Expression(exp_stmt) => match exp_stmt.expression.as_ref().unwrap() {
+                ExpressionNode::Super(super_call) => {
+                    assert_eq!(
+                        super_call.arguments.len(),
+                        2,
+                        "wrong number of arguments. got={}",
+                        super_call.arguments.len()
+                    );
+                    assert_eq!(super_call.print_string(), "super(1, (a + 2))");
+                }
+                other => panic!("expression not SuperCall. got={:?}", other),
+            },
+            other => panic!(
+                "program.statements[0] is not ExpressionStatement. got={:?}",
+                other
+            ),
+        }
+    }
+
+    #[test]
+    fn test_super_call_requires_arguments() {
+        let input = "super;";
+
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+        parser.parse_program();
+
+        assert_eq!(
+            parser.errors(),
+            &vec![String::from("expected next token to  be (, got ; instead")]
+        );
+    }
+
+    #[test]
+    fn test_object_declaration() {
+        let input = r#"
+            object pepita {
+                var energia = 100
+                const nombre = 1
+
+                method energia() = energia
+                method volar(km, extra) {
+                    return self.energia() - km * extra
+                }
+                override method comer(gramos) {
+                    super(gramos)
+                }
+                method abstracto()
+            }
+        "#;
+
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program().unwrap();
+        check_parser_errors(parser);
+
+        assert_eq!(
+            program.statements.len(),
+            1,
+            "statements does not contain 1 statement. got={}",
+            program.statements.len()
+        );
+
+        match &program.statements[0] {
+            StatementNode::Object(object) => {
+                assert_eq!(object.name.value, "pepita");
+                assert_eq!(object.fields.len(), 2);
+                assert_eq!(object.methods.len(), 4);
+
+                let names: Vec<&str> = object
+                    .methods
+                    .iter()
+                    .map(|method| method.name.value.as_str())
+                    .collect();
+                assert_eq!(names, vec!["energia", "volar", "comer", "abstracto"]);
+
+                assert!(!object.methods[1].overrides);
+                assert!(object.methods[2].overrides);
+                assert!(matches!(object.methods[3].body, MethodBody::Abstract));
+
+                assert_eq!(
+                    object.print_string(),
+                    "object pepita { var energia = 100; const nombre = 1; \
+                     method energia() = energia \
+                     method volar(km, extra) { return (self.energia() - (km * extra)); } \
+                     override method comer(gramos) { super(gramos) } \
+                     method abstracto() }"
+                );
+            }
+            other => panic!("stmt is not ObjectDeclaration. got={:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_object_literal() {
+        let input = r#"
+            const stub = object {
+                var llamadas = 0
+                method hola() = llamadas
+            }
+            object inherits Ave { override method volar(km) = super(km * 2) };
+            object golondrina inherits Ave { }
+        "#;
+
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program().unwrap();
+        check_parser_errors(parser);
+
+        assert_eq!(
+            program.statements.len(),
+            3,
+            "statements does not contain 3 statements. got={}",
+            program.statements.len()
+        );
+
+        match &program.statements[0] {
+            StatementNode::Var(var_stmt) => match var_stmt.value.as_ref().unwrap() {
+                ExpressionNode::ObjectLiteral(object) => {
+                    assert!(object.parent.is_none());
+                    assert_eq!(object.fields.len(), 1);
+                    assert_eq!(object.methods.len(), 1);
+                }
+                other => panic!("value is not ObjectLiteral. got={:?}", other),
+            },
+            other => panic!("stmt is not VarStatement. got={:?}", other),
+        }
+
+        match &program.statements[1] {
+            StatementNode::Expression(exp_stmt) => match exp_stmt.expression.as_ref().unwrap() {
+                ExpressionNode::ObjectLiteral(object) => {
+                    assert_eq!(object.parent.as_ref().unwrap().print_string(), "Ave");
+                    assert_eq!(
+                        object.print_string(),
+                        "object inherits Ave { override method volar(km) = super((km * 2)) }"
+                    );
+                }
+                other => panic!("expression is not ObjectLiteral. got={:?}", other),
+            },
+            other => panic!("stmt is not ExpressionStatement. got={:?}", other),
+        }
+
+        match &program.statements[2] {
+            StatementNode::Object(object) => {
+                assert_eq!(object.name.value, "golondrina");
+                assert_eq!(object.parent.as_ref().unwrap().print_string(), "Ave");
+                assert_eq!(object.print_string(), "object golondrina inherits Ave { }");
+            }
+            other => panic!("stmt is not ObjectDeclaration. got={:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_class_and_mixin_declarations() {
+        let input = r#"
+            mixin Volador mixed with Planeador { var altura = 0 method volar() { altura = altura + 1 } }
+            class Golondrina inherits aves.Ave mixed with Volador and Nadador {
+                var energia
+                method energia() = energia
+            }
+            object pepita mixed with Volador { }
+            const tito = new Golondrina(energia = 10, nombre = "tito")
+            new Golondrina()
+        "#;
+
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program().unwrap();
+        check_parser_errors(parser);
+
+        assert_eq!(
             program.statements.len(),
-            1,
-            "program.statement does not contain enough statements. got={}",
+            5,
+            "statements does not contain 5 statements. got={}",
             program.statements.len()
         );
 
         match &program.statements[0] {
-            StatementNode::Expression(exp_stmt) => {
-                assert!(exp_stmt.expression.is_some());
-                match exp_stmt.expression.as_ref().unwrap() {
-                    ExpressionNode::Integer(integer) => {
-                        assert_eq!(
-                            integer.value, 5,
-                            "integer.value not `5`. got={}",
-                            integer.value
-                        );
-                        assert_eq!(
-                            integer.token_literal(),
-                            "5",
-                            "integer.value not `5`. got={}",
-                            integer.token_literal()
-                        );
+            StatementNode::Mixin(mixin) => {
+                assert_eq!(mixin.name.value, "Volador");
+                assert_eq!(mixin.mixins[0].print_string(), "Planeador");
+                assert_eq!(mixin.fields.len(), 1);
+                assert_eq!(mixin.methods.len(), 1);
+            }
+            other => panic!("stmt is not MixinDeclaration. got={:?}", other),
+        }
+
+        match &program.statements[1] {
+            StatementNode::Class(class) => {
+                assert_eq!(class.name.value, "Golondrina");
+                assert_eq!(class.parent.as_ref().unwrap().print_string(), "aves.Ave");
+                assert_eq!(class.mixins.len(), 2);
+                assert_eq!(
+                    class.print_string(),
+                    "class Golondrina inherits aves.Ave mixed with Volador and Nadador \
+                     { var energia; method energia() = energia }"
+                );
+            }
+            other => panic!("stmt is not ClassDeclaration. got={:?}", other),
+        }
+
+        match &program.statements[2] {
+            StatementNode::Object(object) => {
+                assert_eq!(
+                    object.print_string(),
+                    "object pepita mixed with Volador { }"
+                );
+            }
+            other => panic!("stmt is not ObjectDeclaration. got={:?}", other),
+        }
+
+        match &program.statements[3] {
+            StatementNode::Var(var_stmt) => match var_stmt.value.as_ref().unwrap() {
+                ExpressionNode::New(new) => {
+                    assert_eq!(new.class.print_string(), "Golondrina");
+                    assert_eq!(new.arguments.len(), 2);
+                    assert_eq!(
+                        new.print_string(),
+                        r#"new Golondrina(energia = 10, nombre = "tito")"#
+                    );
+                }
+                other => panic!("value is not NewExpression. got={:?}", other),
+            },
+            other => panic!("stmt is not VarStatement. got={:?}", other),
+        }
+
+        assert_eq!(program.statements[4].print_string(), "new Golondrina()");
+    }
+
+    #[test]
+    fn test_new_expression_errors() {
+        let tests = vec![
+            (
+                "new Ave(1)",
+                "expected next token to  be identifier, got number instead",
+            ),
+            (
+                "new Ave(a 1)",
+                "expected next token to  be =, got number instead",
+            ),
+            (
+                "new Ave(a = 1",
+                "expected next token to  be ), got EOF instead",
+            ),
+            (
+                "class Ave mixed Volador { }",
+                "expected next token to  be with, got identifier instead",
+            ),
+        ];
+
+        for (input, expected) in tests {
+            let lexer = Lexer::new(input);
+            let mut parser = Parser::new(lexer);
+            parser.parse_program();
+
+            assert_eq!(
+                parser.errors().first().map(String::as_str),
+                Some(expected),
+                "wrong first error for input={}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_collection_literals() {
+        let tests = vec![
+            ("[]", "[]"),
+            ("[1, 2, 3]", "[1, 2, 3]"),
+            ("[1, 2 * 2, 3 + 3,]", "[1, (2 * 2), (3 + 3)]"),
+            ("[[1], #{2, 3}, a.b()]", "[[1], #{2, 3}, a.b()]"),
+            ("#{}", "#{}"),
+            ("#{1, 2, 3}", "#{1, 2, 3}"),
+            ("#{[1, 2], #{3},}", "#{[1, 2], #{3}}"),
+            ("[1, 2].size()", "[1, 2].size()"),
+        ];
+
+        for (input, expected) in tests {
+            let lexer = Lexer::new(input);
+            let mut parser = Parser::new(lexer);
+            let program = parser.parse_program().unwrap();
+            check_parser_errors(parser);
+
+            assert_eq!(
+                program.print_string(),
+                expected,
+                "expected={}, got={}",
+                expected,
+                program.print_string()
+            );
+        }
+    }
+
+    #[test]
+    fn test_list_literal_elements() {
+        let input = "[1, [2, 3], #{4}]";
+
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program().unwrap();
+        check_parser_errors(parser);
+
+        match &program.statements[0] {
+            StatementNode::Expression(exp_stmt) => match exp_stmt.expression.as_ref().unwrap() {
+                ExpressionNode::List(list) => {
+                    assert_eq!(
+                        list.elements.len(),
+                        3,
+                        "list.elements does not contain 3 elements. got={}",
+                        list.elements.len()
+                    );
+                    assert!(matches!(list.elements[0], ExpressionNode::Integer(_)));
+                    assert!(matches!(list.elements[1], ExpressionNode::List(_)));
+                    match &list.elements[2] {
+                        ExpressionNode::Set(set) => assert_eq!(set.elements.len(), 1),
+                        other => panic!("element is not SetLiteral. got={:?}", other),
                     }
-                    other => panic!("Expression not an IntegerLiteral. got={:?}", other),
                 }
+                other => panic!("expression is not ListLiteral. got={:?}", other),
+            },
+            other => panic!(
+                "program.statements[0] is not ExpressionStatement. got={:?}",
+                other
+            ),
+        }
+    }
+
+    #[test]
+    fn test_trailing_comma_only_in_collections() {
+        let tests = vec![
+            ("[1,,]", "no prefix parse function for , found"),
+            ("#{1; 2}", "expected next token to  be }, got ; instead"),
+            ("a.b(1,)", "no prefix parse function for ) found"),
+        ];
+
+        for (input, expected) in tests {
+            let lexer = Lexer::new(input);
+            let mut parser = Parser::new(lexer);
+            parser.parse_program();
+
+            assert_eq!(
+                parser.errors()[0],
+                expected,
+                "wrong first error for input={}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_throw_statement() {
+        let input = "throw error; throw pepita.cansada()";
+
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program().unwrap();
+        check_parser_errors(parser);
+
+        assert_eq!(
+            program.statements.len(),
+            2,
+            "statements does not contain 2 statements. got={}",
+            program.statements.len()
+        );
+
+        let expected = vec!["throw error", "throw pepita.cansada()"];
+        for (stmt, exp) in program.statements.iter().zip(expected) {
+            match stmt {
+                StatementNode::Throw(throw) => assert_eq!(throw.print_string(), exp),
+                other => panic!("stmt is not ThrowStatement. got={:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_try_expression() {
+        let input = r#"
+            try {
+                pepita.volar(10)
+            } catch e : DomainException {
+                return 1
+            } catch e {
+                return 2
+            } then always {
+                pepita.descansar()
+            }
+        "#;
+
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program().unwrap();
+        check_parser_errors(parser);
+
+        assert_eq!(
+            program.statements.len(),
+            1,
+            "statements does not contain 1 statement. got={}",
+            program.statements.len()
+        );
+
+        match &program.statements[0] {
+            StatementNode::Expression(exp_stmt) => match exp_stmt.expression.as_ref().unwrap() {
+                ExpressionNode::Try(try_exp) => {
+                    assert_eq!(try_exp.body.statements.len(), 1);
+                    assert_eq!(
+                        try_exp.catches.len(),
+                        2,
+                        "try_exp.catches does not contain 2 clauses. got={}",
+                        try_exp.catches.len()
+                    );
+                    assert_eq!(try_exp.catches[0].parameter.value, "e");
+                    assert_eq!(
+                        try_exp.catches[0]
+                            .exception_type
+                            .as_ref()
+                            .unwrap()
+                            .print_string(),
+                        "DomainException"
+                    );
+                    assert!(try_exp.catches[1].exception_type.is_none());
+                    assert!(try_exp.always.is_some());
+                    assert_eq!(
+                        try_exp.print_string(),
+                        "try { pepita.volar(10) } \
+                         catch e : DomainException { return 1; } \
+                         catch e { return 2; } \
+                         then always { pepita.descansar() }"
+                    );
+                }
+                other => panic!("expression is not TryExpression. got={:?}", other),
+            },
+            other => panic!(
+                "program.statements[0] is not ExpressionStatement. got={:?}",
+                other
+            ),
+        }
+    }
+
+    #[test]
+    fn test_try_expression_errors() {
+        let tests = vec![
+            ("try 1", "expected next token to  be {, got number instead"),
+            (
+                "try { } catch { }",
+                "expected next token to  be identifier, got { instead",
+            ),
+            (
+                "try { } then { }",
+                "expected next token to  be always, got { instead",
+            ),
+        ];
+
+        for (input, expected) in tests {
+            let lexer = Lexer::new(input);
+            let mut parser = Parser::new(lexer);
+            parser.parse_program();
+
+            assert_eq!(
+                parser.errors()[0],
+                expected,
+                "wrong first error for input={}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_imports_and_packages() {
+        let input = r#"
+            import example.aves.*
+            import example.aves.pepita;
+
+            package example.entrenadores {
+                object roque inherits example.personas.Persona { }
+                package deportes { }
+            }
+            try { 1 } catch e : wollok.lang.Exception { 2 }
+        "#;
+
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program().unwrap();
+        check_parser_errors(parser);
+
+        assert_eq!(
+            program.imports.len(),
+            2,
+            "program.imports does not contain 2 imports. got={}",
+            program.imports.len()
+        );
+        assert_eq!(
+            program.statements.len(),
+            2,
+            "program.statements does not contain 2 statements. got={}",
+            program.statements.len()
+        );
+
+        let import = &program.imports[0];
+        assert!(import.wildcard);
+        let parts: Vec<&str> = import
+            .name
+            .parts
+            .iter()
+            .map(|part| part.value.as_str())
+            .collect();
+        assert_eq!(parts, vec!["example", "aves"]);
+
+        let import = &program.imports[1];
+        assert!(!import.wildcard);
+        assert_eq!(import.name.print_string(), "example.aves.pepita");
+
+        match &program.statements[0] {
+            StatementNode::Package(package) => {
+                assert_eq!(package.name.print_string(), "example.entrenadores");
+                assert_eq!(package.statements.len(), 2);
+                assert!(matches!(package.statements[1], StatementNode::Package(_)));
+            }
+            other => panic!("stmt is not Package. got={:?}", other),
+        }
+
+        assert_eq!(
+            program.print_string(),
+            "import example.aves.*;import example.aves.pepita;\
+             package example.entrenadores { \
+             object roque inherits example.personas.Persona { } package deportes { } }\
+             try { 1 } catch e : wollok.lang.Exception { 2 }"
+        );
+    }
+
+    #[test]
+    fn test_import_errors() {
+        let tests = vec![
+            (
+                "object pepita { } import aves.*",
+                "imports must appear before any declaration",
+            ),
+            (
+                "import aves.*.pepita",
+                "no prefix parse function for . found",
+            ),
+            (
+                "import 1",
+                "expected next token to  be identifier, got number instead",
+            ),
+            (
+                "package { }",
+                "expected next token to  be identifier, got { instead",
+            ),
+            ("package aves {", "expected }, got EOF instead"),
+        ];
+
+        for (input, expected) in tests {
+            let lexer = Lexer::new(input);
+            let mut parser = Parser::new(lexer);
+            parser.parse_program();
+
+            assert_eq!(
+                parser.errors()[0],
+                expected,
+                "wrong first error for input={}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_program_declaration() {
+        let input = r#"
+            object pepita { }
+
+            program saludo {
+                console.println("hola")
+                pepita.volar(10)
+            }
+        "#;
+
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program().unwrap();
+        check_parser_errors(parser);
+
+        assert_eq!(
+            program.statements.len(),
+            2,
+            "statements does not contain 2 statements. got={}",
+            program.statements.len()
+        );
+
+        match &program.statements[1] {
+            StatementNode::Program(program) => {
+                assert_eq!(program.name.value, "saludo");
+                assert_eq!(program.body.statements.len(), 2);
+                assert_eq!(
+                    program.print_string(),
+                    r#"program saludo { console.println("hola") pepita.volar(10) }"#
+                );
             }
+            other => panic!("stmt is not ProgramDeclaration. got={:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_string_literal_expression() {
+        let input = r#""hola mundo";"#;
+
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program().unwrap();
+        check_parser_errors(parser);
+
+        match &program.statements[0] {
+            StatementNode::Expression(exp_stmt) => match exp_stmt.expression.as_ref().unwrap() {
+                ExpressionNode::String(string) => {
+                    assert_eq!(
+                        string.value, "hola mundo",
+                        "string.value not `hola mundo`. got={}",
+                        string.value
+                    );
+                }
+                other => panic!("expression not StringLiteral. got={:?}", other),
+            },
             other => panic!(
                 "program.statements[0] is not ExpressionStatement. got={:?}",
                 other
@@ -372,6 +2296,115 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_describe_block() {
+        let input = r#"
+            describe "pepita" {
+                var pepita = object { }
+                const energia = 100
+
+                method initialize() {
+                    pepita.comer(10)
+                }
+
+                test "vuela" {
+                    pepita.volar(1)
+                }
+                only test "come" {
+                    pepita.comer(1)
+                }
+            }
+            test "suelto" { }
+        "#;
+
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program().unwrap();
+        check_parser_errors(parser);
+
+        assert_eq!(
+            program.statements.len(),
+            2,
+            "statements does not contain 2 statements. got={}",
+            program.statements.len()
+        );
+
+        match &program.statements[0] {
+            StatementNode::Describe(describe) => {
+                assert_eq!(describe.name.value, "pepita");
+                assert_eq!(describe.fields.len(), 2);
+                assert_eq!(describe.methods.len(), 1);
+                assert!(describe.initialize().is_some());
+
+                let tests: Vec<(&str, bool)> = describe
+                    .tests
+                    .iter()
+                    .map(|test| (test.name.value.as_str(), test.only))
+                    .collect();
+                assert_eq!(tests, vec![("vuela", false), ("come", true)]);
+            }
+            other => panic!("stmt is not DescribeBlock. got={:?}", other),
+        }
+
+        match &program.statements[1] {
+            StatementNode::Test(test) => {
+                assert_eq!(test.name.value, "suelto");
+                assert!(!test.only);
+                assert_eq!(test.print_string(), r#"test "suelto" { }"#);
+            }
+            other => panic!("stmt is not TestDeclaration. got={:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_describe_errors() {
+        let tests = vec![
+            (
+                "describe pepita { }",
+                "expected next token to  be string, got identifier instead",
+            ),
+            (
+                "describe \"a\" { object b { } }",
+                "unexpected object in describe body",
+            ),
+            (
+                "only \"a\" { }",
+                "expected next token to  be test, got string instead",
+            ),
+            (
+                "test vuela { }",
+                "expected next token to  be string, got identifier instead",
+            ),
+        ];
+
+        for (input, expected) in tests {
+            let lexer = Lexer::new(input);
+            let mut parser = Parser::new(lexer);
+            parser.parse_program();
+
+            assert_eq!(
+                parser.errors()[0],
+                expected,
+                "wrong first error for input={}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_unclosed_object_declaration() {
+        let input = "object pepita { method volar() = 1";
+
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+        parser.parse_program();
+
+        assert_eq!(
+            parser.errors(),
+            &vec![String::from("expected }, got EOF instead")]
+        );
+    }
+
     fn test_var_statement(stmt: &StatementNode, expected: &str) {
         assert_eq!(
             stmt.token_literal(),
@@ -402,7 +2435,7 @@ mod test {
     fn check_parser_errors(parser: Parser) {
         let errors = parser.errors();
 
-        if errors.len() == 0 {
+        if errors.is_empty() {
             return;
         }
 
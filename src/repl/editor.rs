@@ -0,0 +1,86 @@
+use crate::repl::Session;
+use rustyline::completion::Completer;
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor};
+use std::cell::RefCell;
+use std::env;
+use std::io;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+const HISTORY_FILE: &str = ".rollok_history";
+
+/// Completes from the same session the REPL evaluates in, so bindings
+/// made on earlier lines show up.
+struct Helper {
+    session: Rc<RefCell<Session>>,
+}
+
+impl Completer for Helper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        Ok(self.session.borrow().completions(line, pos))
+    }
+}
+
+impl Hinter for Helper {
+    type Hint = String;
+}
+
+impl Highlighter for Helper {}
+
+impl Validator for Helper {}
+
+impl rustyline::Helper for Helper {}
+
+fn history_path() -> Option<PathBuf> {
+    env::var_os("HOME").map(|home| PathBuf::from(home).join(HISTORY_FILE))
+}
+
+/// Runs the REPL on the terminal with line editing, tab completion and
+/// history kept in ~/.rollok_history. Ctrl-C drops the current input and
+/// Ctrl-D ends the session.
+pub fn start() -> rustyline::Result<()> {
+    let session = Rc::new(RefCell::new(Session::new()));
+    let mut editor = Editor::<Helper, DefaultHistory>::new()?;
+    editor.set_helper(Some(Helper {
+        session: Rc::clone(&session),
+    }));
+
+    let history = history_path();
+    if let Some(path) = &history {
+        // There is no history to load on the first run.
+        let _ = editor.load_history(path);
+    }
+
+    let mut stdout = io::stdout();
+    loop {
+        let prompt = session.borrow().prompt();
+        match editor.readline(prompt) {
+            Ok(line) => {
+                if !line.trim().is_empty() {
+                    editor.add_history_entry(line.as_str())?;
+                }
+                session.borrow_mut().feed(&line, &mut stdout)?;
+            }
+            Err(ReadlineError::Interrupted) => session.borrow_mut().cancel(),
+            Err(ReadlineError::Eof) => break,
+            Err(err) => return Err(err),
+        }
+    }
+
+    if let Some(path) = &history {
+        editor.save_history(path)?;
+    }
+    Ok(())
+}
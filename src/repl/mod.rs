@@ -0,0 +1,640 @@
+#[cfg(feature = "line-editing")]
+pub mod editor;
+
+use crate::ast;
+use crate::ast::StatementNode;
+use crate::evaluator::Evaluator;
+use crate::lexer2::Lexer;
+use crate::parser::Parser;
+use crate::runtime::value::Value;
+use crate::token::{Token, TokenKind, KEYWORDS};
+use crate::validator::validate;
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::io::{BufRead, Write};
+use std::rc::Rc;
+
+const PROMPT: &str = ">> ";
+const CONTINUATION_PROMPT: &str = ".. ";
+
+const HELP: &str = ":tokens <input>   show the tokens of the input
+:ast <input>      show the syntax tree of the input
+:load <file>      evaluate a file, bringing its declarations into scope
+:reset            forget every declaration and variable
+:env              list the declarations and variables in scope
+:cancel           drop a pending multi-line input
+:help             show this message";
+
+/// The state that survives between inputs: declarations and variables
+/// from earlier lines stay in scope for the next ones.
+struct Session {
+    evaluator: Evaluator<'static>,
+    /// Lines of an input that is not complete yet.
+    pending: String,
+    /// Bindings that come with every evaluator, left out of `:env`.
+    builtins: HashSet<String>,
+}
+
+impl Session {
+    fn new() -> Self {
+        let mut evaluator = Evaluator::new();
+        evaluator.set_file("<repl>");
+        let builtins = evaluator
+            .bindings()
+            .into_iter()
+            .map(|(name, _, _)| name)
+            .collect();
+
+        Self {
+            evaluator,
+            pending: String::new(),
+            builtins,
+        }
+    }
+
+    fn prompt(&self) -> &'static str {
+        if self.pending.is_empty() {
+            PROMPT
+        } else {
+            CONTINUATION_PROMPT
+        }
+    }
+
+    /// Takes one line of input. It is evaluated together with the lines
+    /// before it once they form a complete input; `:cancel` drops them.
+    fn feed(&mut self, line: &str, out: &mut impl Write) -> io::Result<()> {
+        if !self.pending.is_empty() && line.trim() == ":cancel" {
+            self.cancel();
+            return writeln!(out, "input cancelled");
+        }
+
+        if self.pending.is_empty() && line.trim_start().starts_with(':') {
+            return self.command(line.trim(), out);
+        }
+
+        self.pending.push_str(line);
+        if !self.pending.ends_with('\n') {
+            self.pending.push('\n');
+        }
+
+        if self.pending.trim().is_empty() {
+            self.pending.clear();
+            return Ok(());
+        }
+        if is_incomplete(&self.pending) {
+            return Ok(());
+        }
+
+        let input = std::mem::take(&mut self.pending);
+        self.eval(&input, out)
+    }
+
+    /// Drops the lines of a pending multi-line input.
+    fn cancel(&mut self) {
+        self.pending.clear();
+    }
+
+    /// Candidates to complete the word that ends at `pos`, and where that
+    /// word starts. After a dot they are the messages the receiver
+    /// understands; anywhere else, keywords and bindings in scope.
+    #[cfg_attr(not(feature = "line-editing"), allow(dead_code))]
+    fn completions(&self, line: &str, pos: usize) -> (usize, Vec<String>) {
+        let before = &line[..pos];
+        let start = before
+            .rfind(|ch: char| !is_identifier_char(ch))
+            .map_or(0, |index| index + 1);
+        let word = &before[start..];
+
+        let candidates: Vec<String> = match before[..start].strip_suffix('.') {
+            Some(receiver) => match self.receiver(receiver) {
+                Some(value) => self.evaluator.message_names(&value),
+                None => vec![],
+            },
+            None => KEYWORDS
+                .iter()
+                .map(|(keyword, _)| String::from(*keyword))
+                .chain(
+                    self.evaluator
+                        .bindings()
+                        .into_iter()
+                        .map(|(name, _, _)| name),
+                )
+                .collect(),
+        };
+
+        let mut candidates: Vec<String> = candidates
+            .into_iter()
+            .filter(|candidate| candidate.starts_with(word))
+            .collect();
+        candidates.sort();
+        candidates.dedup();
+
+        (start, candidates)
+    }
+
+    /// Works out what the text before a dot evaluates to, for the simple
+    /// cases that do not need evaluating anything: a binding or a literal.
+    #[cfg_attr(not(feature = "line-editing"), allow(dead_code))]
+    fn receiver(&self, text: &str) -> Option<Value> {
+        let text = text.trim_end();
+        let start = text
+            .rfind(|ch: char| !is_identifier_char(ch))
+            .map_or(0, |index| index + 1);
+        let word = &text[start..];
+
+        match text.chars().last()? {
+            '"' | '\'' => Some(Value::String(String::new())),
+            ']' => Some(Value::List(Rc::new(RefCell::new(vec![])))),
+            _ if word == "true" || word == "false" => Some(Value::Boolean(true)),
+            _ if !word.is_empty() && word.chars().all(|ch| ch.is_ascii_digit()) => {
+                Some(Value::Number(0.0))
+            }
+            _ => self.evaluator.lookup(word),
+        }
+    }
+
+    fn command(&mut self, line: &str, out: &mut impl Write) -> io::Result<()> {
+        let (command, argument) = match line.split_once(char::is_whitespace) {
+            Some((command, argument)) => (command, argument.trim()),
+            None => (line, ""),
+        };
+
+        match (command, argument) {
+            (":tokens", input) if !input.is_empty() => Self::tokens(input, out),
+            (":ast", input) if !input.is_empty() => Self::ast(input, out),
+            (":load", file) if !file.is_empty() => self.load(file, out),
+            (":reset", "") => {
+                *self = Self::new();
+                writeln!(out, "environment reset")
+            }
+            (":env", "") => self.env(out),
+            (":help", "") => writeln!(out, "{}", HELP),
+            (":cancel", "") => writeln!(out, "nothing to cancel"),
+            (":tokens" | ":ast" | ":load" | ":reset" | ":env" | ":help" | ":cancel", _) => {
+                writeln!(out, "error: wrong arguments for {}, try :help", command)
+            }
+            (command, _) => writeln!(out, "error: unknown command {}, try :help", command),
+        }
+    }
+
+    fn tokens(input: &str, out: &mut impl Write) -> io::Result<()> {
+        let mut lexer = Lexer::new(input);
+
+        loop {
+            let token = lexer.next_token();
+            if token.kind == TokenKind::EOF {
+                return Ok(());
+            }
+            writeln!(out, "{token:?}")?;
+        }
+    }
+
+    fn ast(input: &str, out: &mut impl Write) -> io::Result<()> {
+        let mut parser = Parser::new(Lexer::new(input));
+        let program = parser.parse_program();
+
+        if !parser.errors().is_empty() {
+            for error in parser.errors() {
+                writeln!(out, "error: {}", error)?;
+            }
+            return Ok(());
+        }
+
+        match program {
+            Some(program) => write!(out, "{}", ast::dump(&program)),
+            None => Ok(()),
+        }
+    }
+
+    fn load(&mut self, file: &str, out: &mut impl Write) -> io::Result<()> {
+        let input = match fs::read_to_string(file) {
+            Ok(input) => input,
+            Err(e) => return writeln!(out, "error: could not read {}: {}", file, e),
+        };
+
+        self.evaluator.set_file(file);
+        let result = self.eval(&input, out);
+        self.evaluator.set_file("<repl>");
+        result
+    }
+
+    fn env(&self, out: &mut impl Write) -> io::Result<()> {
+        for (name, value, constant) in self.evaluator.bindings() {
+            if self.builtins.contains(&name) {
+                continue;
+            }
+
+            let keyword = if constant { "const" } else { "var" };
+            writeln!(out, "{} {} = {}", keyword, name, value)?;
+        }
+        Ok(())
+    }
+
+    /// Evaluates one input, writing its printString if it ends with an
+    /// expression, or the errors it ran into.
+    fn eval(&mut self, input: &str, out: &mut impl Write) -> io::Result<()> {
+        let mut parser = Parser::new(Lexer::new(input));
+        let program = parser.parse_program();
+
+        if !parser.errors().is_empty() {
+            for error in parser.errors() {
+                writeln!(out, "error: {}", error)?;
+            }
+            return Ok(());
+        }
+        let program = match program {
+            Some(program) => program,
+            None => return Ok(()),
+        };
+
+        let errors = validate(&program);
+        if !errors.is_empty() {
+            for error in errors {
+                writeln!(out, "error: {}", error)?;
+            }
+            return Ok(());
+        }
+
+        let result = self.evaluator.eval_program(&program).and_then(|value| {
+            match program.statements.last() {
+                Some(StatementNode::Expression(_)) => self.evaluator.describe(&value).map(Some),
+                _ => Ok(None),
+            }
+        });
+
+        match result {
+            Ok(Some(value)) => writeln!(out, "{}", value),
+            Ok(None) => Ok(()),
+            // A single frame is just the top level, which adds nothing to
+            // a one-line input.
+            Err(error) if error.stack_trace.len() <= 1 => {
+                writeln!(out, "error: {}", error.message)
+            }
+            Err(error) => writeln!(out, "error: {}", error),
+        }
+    }
+}
+
+/// Whether the input stops halfway through: brackets or a string left
+/// open, a trailing operator, or the parser running out of tokens.
+fn is_incomplete(input: &str) -> bool {
+    let mut lexer = Lexer::new(input);
+    let mut depth = 0;
+    let mut last = None;
+
+    loop {
+        let token = lexer.next_token();
+        match token.kind {
+            TokenKind::EOF => break,
+            TokenKind::LeftParen | TokenKind::LeftBracket | TokenKind::LeftBrace => depth += 1,
+            TokenKind::RightParen | TokenKind::RightBracket | TokenKind::RightBrace => depth -= 1,
+            _ => {}
+        }
+        last = Some(token);
+    }
+
+    if depth > 0 {
+        return true;
+    }
+    match last {
+        Some(token) if is_unterminated_string(input, &token) || continues(token.kind) => {
+            return true
+        }
+        None => return false,
+        _ => {}
+    }
+
+    let mut parser = Parser::new(Lexer::new(input));
+    parser.parse_program();
+    parser.errors().iter().any(|error| error.contains("EOF"))
+}
+
+/// The lexer gives an error token for a string that reaches the end of
+/// the input; tell it apart from a stray character by where it starts.
+fn is_unterminated_string(input: &str, token: &Token) -> bool {
+    if token.kind != TokenKind::Error {
+        return false;
+    }
+
+    let start = token.span.start;
+    input
+        .lines()
+        .nth(start.line - 1)
+        .and_then(|line| line.chars().nth(start.column - 1))
+        .is_some_and(|ch| ch == '"' || ch == '\'')
+}
+
+#[cfg_attr(not(feature = "line-editing"), allow(dead_code))]
+fn is_identifier_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_'
+}
+
+/// Tokens that cannot end an input because something must follow them.
+fn continues(kind: TokenKind) -> bool {
+    matches!(
+        kind,
+        TokenKind::Plus
+            | TokenKind::Minus
+            | TokenKind::Multiply
+            | TokenKind::Divide
+            | TokenKind::Asterisk
+            | TokenKind::Modulo
+            | TokenKind::Assign
+            | TokenKind::Equals
+            | TokenKind::Eq
+            | TokenKind::NotEq
+            | TokenKind::GreaterThan
+            | TokenKind::LessThan
+            | TokenKind::GreaterEq
+            | TokenKind::LessEq
+            | TokenKind::And
+            | TokenKind::Or
+            | TokenKind::Not
+            | TokenKind::Bang
+            | TokenKind::Dot
+            | TokenKind::DotDot
+            | TokenKind::Arrow
+            | TokenKind::FatArrow
+            | TokenKind::Comma
+            | TokenKind::Colon
+    )
+}
+
+/// Reads inputs from `input` until it runs out, evaluating each one and
+/// writing prompts, results and errors to `output`. Fails if either side
+/// does, for example when the output is a closed pipe.
+pub fn start(mut input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+    let mut session = Session::new();
+
+    loop {
+        write!(output, "{}", session.prompt())?;
+        output.flush()?;
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            // Leave the terminal on a fresh line after Ctrl-D.
+            return writeln!(output);
+        }
+
+        session.feed(&line, &mut output)?;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::repl::{is_incomplete, Session, CONTINUATION_PROMPT, PROMPT};
+
+    fn check(session: &mut Session, input: &str, expected: &str) {
+        let mut out = vec![];
+        session.eval(input, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            expected,
+            "input: {}",
+            input
+        );
+    }
+
+    #[test]
+    fn test_persistent_environment() {
+        let mut session = Session::new();
+
+        check(&mut session, "var x = 5", "");
+        check(&mut session, "x * 2", "10\n");
+        check(&mut session, "x = x + 1", "");
+        check(&mut session, "x", "6\n");
+        check(
+            &mut session,
+            "object pepita { var energia = 10 method energia() = energia }",
+            "",
+        );
+        check(&mut session, "pepita.energia()", "10\n");
+        check(&mut session, "\"hola\"", "\"hola\"\n");
+        check(&mut session, "[1, 2].map({ n => n * x })", "[6, 12]\n");
+        check(&mut session, "pepita", "pepita\n");
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut session = Session::new();
+
+        check(
+            &mut session,
+            "var = 5",
+            "error: expected next token to  be identifier, got = instead\nerror: no prefix parse function for = found\n",
+        );
+        check(&mut session, "y", "error: reference y not found\n");
+        check(
+            &mut session,
+            "self",
+            "error: self cannot be used at program top level\n",
+        );
+        check(&mut session, "object a { method m() = self.n() }", "");
+        check(
+            &mut session,
+            "a.m()",
+            "error: MessageNotUnderstoodException: a does not understand n/0\n    at a.m() [<repl>:1:30]\n    at top level [<repl>:1:3]\n",
+        );
+        check(&mut session, "1 + 1", "2\n");
+    }
+
+    #[test]
+    fn test_is_incomplete() {
+        let tests = vec![
+            ("1 + 2", false),
+            ("object pepita {", true),
+            ("object pepita { method volar() {", true),
+            ("object pepita { }", false),
+            ("[1, 2", true),
+            ("(1 + 2", true),
+            ("\"hola", true),
+            ("'hola", true),
+            ("\"hola\"", false),
+            ("1 +", true),
+            ("pepita.", true),
+            ("var x =", true),
+            ("[1, 2].map({ n =>", true),
+            ("if (true) 1 else", true),
+            ("try { 1 } catch e", true),
+            ("1 @ 2", false),
+            ("1 }", false),
+            ("", false),
+        ];
+
+        for (input, expected) in tests {
+            assert_eq!(is_incomplete(input), expected, "input: {}", input);
+        }
+    }
+
+    #[test]
+    fn test_multi_line_input() {
+        let mut session = Session::new();
+        let mut out = vec![];
+
+        assert_eq!(session.prompt(), PROMPT);
+        session.feed("object pepita {\n", &mut out).unwrap();
+        assert_eq!(session.prompt(), CONTINUATION_PROMPT);
+        session.feed("  var energia = 10\n", &mut out).unwrap();
+        session.feed("  method energia() =\n", &mut out).unwrap();
+        session.feed("    energia\n", &mut out).unwrap();
+        session.feed("}\n", &mut out).unwrap();
+        assert_eq!(session.prompt(), PROMPT);
+        session.feed("pepita.energia() +\n", &mut out).unwrap();
+        session.feed("1\n", &mut out).unwrap();
+
+        session.feed("[1,\n", &mut out).unwrap();
+        session.feed(":cancel\n", &mut out).unwrap();
+        assert_eq!(session.prompt(), PROMPT);
+        session.feed("\n", &mut out).unwrap();
+        assert_eq!(session.prompt(), PROMPT);
+
+        assert_eq!(String::from_utf8(out).unwrap(), "11\ninput cancelled\n");
+    }
+
+    fn run(session: &mut Session, line: &str) -> String {
+        let mut out = vec![];
+        session.feed(line, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_tokens_command() {
+        let mut session = Session::new();
+
+        let out = run(&mut session, ":tokens x + 1");
+        let kinds: Vec<&str> = out
+            .lines()
+            .map(|line| line.split(',').next().unwrap())
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                "Token { kind: Identifier",
+                "Token { kind: Plus",
+                "Token { kind: Number"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ast_command() {
+        let mut session = Session::new();
+
+        assert_eq!(
+            run(&mut session, ":ast pepita.volar(1)"),
+            "Send volar/1 @1:8-1:13\n  Identifier pepita @1:1-1:7\n  Integer 1 @1:14-1:15\n"
+        );
+
+        assert_eq!(
+            run(&mut session, ":ast 1 +"),
+            "error: no prefix parse function for EOF found\n"
+        );
+    }
+
+    #[test]
+    fn test_load_command() {
+        let path = std::env::temp_dir().join(format!("rollok-repl-{}.wlk", std::process::id()));
+        std::fs::write(
+            &path,
+            "object pepita {\n  method volar() = self.cantar()\n}",
+        )
+        .unwrap();
+        let file = path.display().to_string();
+
+        let mut session = Session::new();
+        assert_eq!(run(&mut session, &format!(":load {}", file)), "");
+        assert_eq!(run(&mut session, "pepita"), "pepita\n");
+        assert_eq!(
+            run(&mut session, "pepita.volar()"),
+            format!(
+                "error: MessageNotUnderstoodException: pepita does not understand cantar/0\n    at pepita.volar() [{}:2:25]\n    at top level [<repl>:1:8]\n",
+                file
+            )
+        );
+
+        assert!(run(&mut session, ":load /nonexistent.wlk")
+            .starts_with("error: could not read /nonexistent.wlk: "));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_reset_and_env_commands() {
+        let mut session = Session::new();
+
+        assert_eq!(run(&mut session, ":env"), "");
+        run(&mut session, "var x = 1");
+        run(&mut session, "object pepita { }");
+        run(&mut session, "class Ave { }");
+        assert_eq!(
+            run(&mut session, ":env"),
+            "const Ave = Ave\nconst pepita = pepita\nvar x = 1\n"
+        );
+
+        assert_eq!(run(&mut session, ":reset"), "environment reset\n");
+        assert_eq!(run(&mut session, ":env"), "");
+        assert_eq!(run(&mut session, "x"), "error: reference x not found\n");
+    }
+
+    #[test]
+    fn test_help_and_unknown_commands() {
+        let mut session = Session::new();
+
+        assert!(run(&mut session, ":help").starts_with(":tokens <input>"));
+        assert_eq!(
+            run(&mut session, ":quit"),
+            "error: unknown command :quit, try :help\n"
+        );
+        assert_eq!(
+            run(&mut session, ":load"),
+            "error: wrong arguments for :load, try :help\n"
+        );
+        assert_eq!(
+            run(&mut session, ":env x"),
+            "error: wrong arguments for :env, try :help\n"
+        );
+        assert_eq!(run(&mut session, ":cancel"), "nothing to cancel\n");
+    }
+
+    #[test]
+    fn test_completions() {
+        let mut session = Session::new();
+        run(
+            &mut session,
+            "object pepita { method volar() = 1 method vive() = true }",
+        );
+        run(&mut session, "var peso = 3");
+
+        let (start, candidates) = session.completions("pe", 2);
+        assert_eq!(
+            (start, candidates),
+            (0, vec![String::from("pepita"), String::from("peso")])
+        );
+
+        let (start, candidates) = session.completions("var x = ob", 10);
+        assert_eq!((start, candidates), (8, vec![String::from("object")]));
+
+        let (start, candidates) = session.completions("pepita.v", 8);
+        assert_eq!(
+            (start, candidates),
+            (7, vec![String::from("vive"), String::from("volar")])
+        );
+
+        let (_, candidates) = session.completions("\"hola\".toU", 10);
+        assert_eq!(candidates, vec![String::from("toUpperCase")]);
+
+        let (_, candidates) = session.completions("[1, 2].fil", 10);
+        assert_eq!(candidates, vec![String::from("filter")]);
+
+        let (_, candidates) = session.completions("3.ev", 4);
+        assert_eq!(candidates, vec![String::from("even")]);
+
+        let (_, candidates) = session.completions("nadie.x", 7);
+        assert!(candidates.is_empty());
+
+        let (start, candidates) = session.completions("pepita.volar() + pe", 19);
+        assert_eq!((start, candidates.len()), (17, 2));
+    }
+}
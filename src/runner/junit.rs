@@ -0,0 +1,161 @@
+use crate::runner::{Outcome, Report, TestResult};
+use std::io;
+use std::io::Write;
+use std::time::Duration;
+
+struct Suite<'a> {
+    name: &'a str,
+    file: &'a str,
+    results: Vec<&'a TestResult>,
+}
+
+/// Writes the report as JUnit XML: one testsuite per describe (tests
+/// outside a describe are grouped by file) and one testcase per test.
+pub fn write(report: &Report, out: &mut impl Write) -> io::Result<()> {
+    writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        out,
+        r#"<testsuites tests="{}" failures="{}" errors="{}" time="{}">"#,
+        report.results.len() + report.file_errors.len(),
+        report.failed(),
+        report.errored() + report.file_errors.len(),
+        seconds(report.results.iter().map(|result| result.duration).sum())
+    )?;
+
+    for suite in suites(report) {
+        let count = |failed: fn(&Outcome) -> bool| {
+            suite
+                .results
+                .iter()
+                .filter(|result| failed(&result.outcome))
+                .count()
+        };
+
+        writeln!(
+            out,
+            r#"  <testsuite name="{}" file="{}" tests="{}" failures="{}" errors="{}" time="{}">"#,
+            escape(suite.name),
+            escape(suite.file),
+            suite.results.len(),
+            count(|outcome| matches!(outcome, Outcome::Failed(_))),
+            count(|outcome| matches!(outcome, Outcome::Errored(_))),
+            seconds(suite.results.iter().map(|result| result.duration).sum())
+        )?;
+
+        for result in suite.results {
+            let testcase = format!(
+                r#"<testcase name="{}" classname="{}" file="{}" line="{}" time="{}""#,
+                escape(&result.name),
+                escape(suite.name),
+                escape(&result.file),
+                result.position.line,
+                seconds(result.duration)
+            );
+
+            let (element, error) = match &result.outcome {
+                Outcome::Passed => {
+                    writeln!(out, "    {}/>", testcase)?;
+                    continue;
+                }
+                Outcome::Failed(error) => ("failure", error),
+                Outcome::Errored(error) => ("error", error),
+            };
+
+            writeln!(out, "    {}>", testcase)?;
+            writeln!(
+                out,
+                r#"      <{} message="{}" type="{}">{}</{}>"#,
+                element,
+                escape(&error.message),
+                escape(error.exception.as_deref().unwrap_or("Error")),
+                escape(&error.to_string()),
+                element
+            )?;
+            writeln!(out, "    </testcase>")?;
+        }
+
+        writeln!(out, "  </testsuite>")?;
+    }
+
+    for file_error in &report.file_errors {
+        let errors = file_error.errors.join("\n");
+        writeln!(
+            out,
+            r#"  <testsuite name="{}" file="{}" tests="1" failures="0" errors="1" time="0.000">"#,
+            escape(&file_error.file),
+            escape(&file_error.file)
+        )?;
+        writeln!(
+            out,
+            r#"    <testcase name="could not be run" classname="{}" file="{}" time="0.000">"#,
+            escape(&file_error.file),
+            escape(&file_error.file)
+        )?;
+        writeln!(
+            out,
+            r#"      <error message="{}" type="ParseError">{}</error>"#,
+            escape(&file_error.errors[0]),
+            escape(&errors)
+        )?;
+        writeln!(out, "    </testcase>")?;
+        writeln!(out, "  </testsuite>")?;
+    }
+
+    writeln!(out, "</testsuites>")
+}
+
+fn suites(report: &Report) -> Vec<Suite<'_>> {
+    let mut suites: Vec<Suite> = vec![];
+
+    for result in &report.results {
+        let name = result.describe.as_deref().unwrap_or(&result.file);
+        match suites
+            .iter_mut()
+            .find(|suite| suite.name == name && suite.file == result.file)
+        {
+            Some(suite) => suite.results.push(result),
+            None => suites.push(Suite {
+                name,
+                file: &result.file,
+                results: vec![result],
+            }),
+        }
+    }
+
+    suites
+}
+
+fn seconds(duration: Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64())
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            ch => out.push(ch),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use crate::runner::{sample_report, Format};
+
+    #[test]
+    fn test_golden() {
+        let mut out = vec![];
+        sample_report().write(Format::JUnit, &mut out).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            include_str!("testdata/report.xml")
+        );
+    }
+}
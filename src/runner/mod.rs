@@ -0,0 +1,626 @@
+mod junit;
+mod tap;
+
+use crate::ast::{DescribeBlock, Node, Program, StatementNode, TestDeclaration};
+use crate::evaluator::{Evaluator, RuntimeError};
+use crate::lexer2::Lexer;
+use crate::parser::Parser;
+use crate::token::Position;
+use crate::validator::validate;
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Outcome {
+    Passed,
+    /// An assertion did not hold.
+    Failed(RuntimeError),
+    /// Anything else went wrong while running the test.
+    Errored(RuntimeError),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct TestResult {
+    pub file: String,
+    pub describe: Option<String>,
+    pub name: String,
+    pub position: Position,
+    pub duration: Duration,
+    pub outcome: Outcome,
+}
+
+impl TestResult {
+    pub fn full_name(&self) -> String {
+        match &self.describe {
+            Some(describe) => format!("{} > {}", describe, self.name),
+            None => self.name.clone(),
+        }
+    }
+}
+
+/// How a report is written out: a summary for people, or JUnit XML and TAP
+/// for CI tools.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Format {
+    Summary,
+    JUnit,
+    Tap,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format {
+            "summary" => Ok(Self::Summary),
+            "junit" => Ok(Self::JUnit),
+            "tap" => Ok(Self::Tap),
+            other => Err(format!(
+                "unknown report format {}, expected one of: summary, junit, tap",
+                other
+            )),
+        }
+    }
+}
+
+/// A file that could not be run because it does not parse or validate.
+#[derive(Debug, PartialEq, Clone)]
+pub struct FileError {
+    pub file: String,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct Report {
+    pub results: Vec<TestResult>,
+    pub file_errors: Vec<FileError>,
+}
+
+impl Report {
+    fn count(&self, matches: fn(&Outcome) -> bool) -> usize {
+        self.results
+            .iter()
+            .filter(|result| matches(&result.outcome))
+            .count()
+    }
+
+    pub fn passed(&self) -> usize {
+        self.count(|outcome| matches!(outcome, Outcome::Passed))
+    }
+
+    pub fn failed(&self) -> usize {
+        self.count(|outcome| matches!(outcome, Outcome::Failed(_)))
+    }
+
+    pub fn errored(&self) -> usize {
+        self.count(|outcome| matches!(outcome, Outcome::Errored(_)))
+    }
+
+    pub fn success(&self) -> bool {
+        self.file_errors.is_empty() && self.passed() == self.results.len()
+    }
+
+    pub fn write(&self, format: Format, out: &mut impl Write) -> io::Result<()> {
+        match format {
+            Format::Summary => self.write_summary(out),
+            Format::JUnit => junit::write(self, out),
+            Format::Tap => tap::write(self, out),
+        }
+    }
+
+    pub fn write_summary(&self, out: &mut impl Write) -> io::Result<()> {
+        for result in &self.results {
+            let mark = match result.outcome {
+                Outcome::Passed => "✓",
+                Outcome::Failed(_) => "✗",
+                Outcome::Errored(_) => "!",
+            };
+            writeln!(out, "{} {}", mark, result.full_name())?;
+        }
+
+        let failures: Vec<&TestResult> = self
+            .results
+            .iter()
+            .filter(|result| result.outcome != Outcome::Passed)
+            .collect();
+
+        if !failures.is_empty() {
+            writeln!(out, "\nFailures:")?;
+        }
+        for (index, result) in failures.iter().enumerate() {
+            if let Outcome::Failed(error) | Outcome::Errored(error) = &result.outcome {
+                writeln!(
+                    out,
+                    "\n{}) {} [{}:{}]",
+                    index + 1,
+                    result.full_name(),
+                    result.file,
+                    result.position
+                )?;
+                for line in error.to_string().lines() {
+                    writeln!(out, "   {}", line)?;
+                }
+            }
+        }
+
+        for file_error in &self.file_errors {
+            writeln!(out, "\n{} could not be run:", file_error.file)?;
+            for error in &file_error.errors {
+                writeln!(out, "   {}", error)?;
+            }
+        }
+
+        writeln!(
+            out,
+            "\n{} tests: {} passed, {} failed, {} errored",
+            self.results.len(),
+            self.passed(),
+            self.failed(),
+            self.errored()
+        )
+    }
+}
+
+/// Finds the .wtest files under a directory, in a stable order. A file path
+/// is returned as is.
+pub fn discover(path: &Path) -> io::Result<Vec<PathBuf>> {
+    if path.is_file() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+
+    let mut files = vec![];
+    for entry in fs::read_dir(path)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            files.extend(discover(&path)?);
+        } else if path
+            .extension()
+            .is_some_and(|extension| extension == "wtest")
+        {
+            files.push(path);
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+pub fn run(path: &Path) -> io::Result<Report> {
+    let mut report = Report::default();
+
+    for file in discover(path)? {
+        let input = fs::read_to_string(&file)?;
+        let name = file.display().to_string();
+
+        match run_source(&name, &input) {
+            Ok(results) => report.results.extend(results),
+            Err(errors) => report.file_errors.push(FileError { file: name, errors }),
+        }
+    }
+
+    Ok(report)
+}
+
+/// Runs every test in a file, or only the ones marked `only` if there are
+/// any. Each test gets a fresh evaluator with the file's declarations and
+/// those of the files it imports.
+pub fn run_source(file: &str, input: &str) -> Result<Vec<TestResult>, Vec<String>> {
+    let program = parse(input)?;
+
+    let mut imports = vec![];
+    load_imports(file, &program, &mut HashSet::new(), &mut imports)?;
+
+    let mut tests = vec![];
+    collect_tests(&program.statements, &mut tests);
+    if tests.iter().any(|(_, test)| test.only) {
+        tests.retain(|(_, test)| test.only);
+    }
+
+    Ok(tests
+        .into_iter()
+        .map(|(describe, test)| run_test(file, &imports, &program, describe, test))
+        .collect())
+}
+
+fn parse(input: &str) -> Result<Program, Vec<String>> {
+    let mut parser = Parser::new(Lexer::new(input));
+    let program = match parser.parse_program() {
+        Some(program) if parser.errors().is_empty() => program,
+        _ => return Err(parser.errors().clone()),
+    };
+
+    let errors = validate(&program);
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    Ok(program)
+}
+
+/// A file brought in by an import, parsed and validated.
+struct Imported {
+    file: String,
+    program: Program,
+}
+
+/// Loads the files a program imports, each one after its own imports.
+/// `import aves.pepita` and `import aves.*` both load aves.wlk from the
+/// directory of the importing file. wollok.lang is built in.
+fn load_imports(
+    file: &str,
+    program: &Program,
+    seen: &mut HashSet<PathBuf>,
+    imports: &mut Vec<Imported>,
+) -> Result<(), Vec<String>> {
+    let dir = Path::new(file).parent().unwrap_or(Path::new(""));
+
+    for import in &program.imports {
+        let mut parts: Vec<&str> = import
+            .name
+            .parts
+            .iter()
+            .map(|part| part.value.as_str())
+            .collect();
+        if parts == ["wollok", "lang"] {
+            continue;
+        }
+        if !import.wildcard && parts.len() > 1 {
+            parts.pop();
+        }
+
+        let path = dir.join(parts.join("/")).with_extension("wlk");
+        if !seen.insert(path.clone()) {
+            continue;
+        }
+
+        let name = path.display().to_string();
+        let input = fs::read_to_string(&path).map_err(|error| {
+            vec![format!(
+                "cannot import {}: {}: {}",
+                import.name.print_string(),
+                name,
+                error
+            )]
+        })?;
+        let program = parse(&input).map_err(|errors| {
+            errors
+                .into_iter()
+                .map(|error| format!("{}: {}", name, error))
+                .collect::<Vec<String>>()
+        })?;
+
+        load_imports(&name, &program, seen, imports)?;
+        imports.push(Imported {
+            file: name,
+            program,
+        });
+    }
+
+    Ok(())
+}
+
+fn collect_tests<'a>(
+    statements: &'a [StatementNode],
+    tests: &mut Vec<(Option<&'a DescribeBlock>, &'a TestDeclaration)>,
+) {
+    for stmt in statements {
+        match stmt {
+            StatementNode::Test(test) => tests.push((None, test)),
+            StatementNode::Describe(describe) => {
+                tests.extend(describe.tests.iter().map(|test| (Some(describe), test)))
+            }
+            StatementNode::Package(package) => collect_tests(&package.statements, tests),
+            _ => {}
+        }
+    }
+}
+
+fn run_test(
+    file: &str,
+    imports: &[Imported],
+    program: &Program,
+    describe: Option<&DescribeBlock>,
+    test: &TestDeclaration,
+) -> TestResult {
+    let start = Instant::now();
+    // Reports go to stdout, so what tests print must not end up there.
+    let mut evaluator = Evaluator::with_output(io::stderr());
+
+    let result = imports
+        .iter()
+        .try_for_each(|imported| {
+            evaluator.set_file(&imported.file);
+            evaluator.eval_program(&imported.program).map(|_| ())
+        })
+        .and_then(|_| {
+            evaluator.set_file(file);
+            evaluator.eval_program(program)
+        })
+        .and_then(|_| evaluator.run_test(describe, test));
+    let duration = start.elapsed();
+
+    let outcome = match result {
+        Ok(_) => Outcome::Passed,
+        Err(error) if error.exception.as_deref() == Some("AssertionException") => {
+            Outcome::Failed(error)
+        }
+        Err(error) => Outcome::Errored(error),
+    };
+
+    TestResult {
+        file: String::from(file),
+        describe: describe.map(|describe| describe.name.value.clone()),
+        name: test.name.value.clone(),
+        position: test.token.span.start,
+        duration,
+        outcome,
+    }
+}
+
+/// A report with every kind of outcome and fixed timings, for the golden
+/// tests of each format.
+#[cfg(test)]
+fn sample_report() -> Report {
+    use crate::runtime::trace::StackFrame;
+
+    let result = |describe: Option<&str>, name: &str, line, micros, outcome| TestResult {
+        file: String::from(if describe.is_some() {
+            "aves/pepita.wtest"
+        } else {
+            "ok.wtest"
+        }),
+        describe: describe.map(String::from),
+        name: String::from(name),
+        position: Position { line, column: 5 },
+        duration: Duration::from_micros(micros),
+        outcome,
+    };
+    let error = |message: &str, exception: &str, test: &str, line, column| RuntimeError {
+        message: String::from(message),
+        exception: Some(String::from(exception)),
+        stack_trace: vec![StackFrame {
+            description: format!("test \"{}\"", test),
+            file: String::from("aves/pepita.wtest"),
+            position: Position { line, column },
+        }],
+    };
+
+    Report {
+        results: vec![
+            result(Some("pepita"), "vuela", 8, 1500, Outcome::Passed),
+            result(
+                Some("pepita"),
+                "se cansa",
+                17,
+                2250,
+                Outcome::Failed(error(
+                    "AssertionException: expected 0 but found 100",
+                    "AssertionException",
+                    "se cansa",
+                    18,
+                    16,
+                )),
+            ),
+            result(
+                Some("pepita"),
+                "canta",
+                21,
+                500,
+                Outcome::Errored(error(
+                    "MessageNotUnderstoodException: \"pio\" does not understand <</1",
+                    "MessageNotUnderstoodException",
+                    "canta",
+                    22,
+                    20,
+                )),
+            ),
+            result(None, "ok", 1, 0, Outcome::Passed),
+        ],
+        file_errors: vec![FileError {
+            file: String::from("roto.wtest"),
+            errors: vec![String::from("expected }, got EOF instead")],
+        }],
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::runner::{discover, run, run_source, Format, Outcome};
+    use std::fs;
+
+    const AVES: &str = r#"object pepita {
+    var energia = 100
+    method volar(km) { energia = energia - km }
+    method energia() = energia
+}
+
+describe "pepita" {
+    test "vuela" {
+        pepita.volar(10)
+        assert.equals(90, pepita.energia())
+    }
+
+    test "arranca con energia" {
+        assert.equals(100, pepita.energia())
+    }
+
+    test "se cansa" {
+        assert.equals(0, pepita.energia())
+    }
+
+    test "explota" {
+        pepita.cantar()
+    }
+}
+"#;
+
+    #[test]
+    fn test_run_source() {
+        let results = run_source("aves.wtest", AVES).unwrap();
+        let names: Vec<String> = results.iter().map(|result| result.full_name()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "pepita > vuela",
+                "pepita > arranca con energia",
+                "pepita > se cansa",
+                "pepita > explota"
+            ]
+        );
+
+        assert_eq!(results[0].outcome, Outcome::Passed);
+        assert_eq!(results[1].outcome, Outcome::Passed);
+        match &results[2].outcome {
+            Outcome::Failed(error) => {
+                assert_eq!(
+                    error.message,
+                    "AssertionException: expected 0 but found 100"
+                )
+            }
+            other => panic!("outcome is not Failed. got={:?}", other),
+        }
+        match &results[3].outcome {
+            Outcome::Errored(error) => assert_eq!(
+                error.message,
+                "MessageNotUnderstoodException: pepita does not understand cantar/0"
+            ),
+            other => panic!("outcome is not Errored. got={:?}", other),
+        }
+        assert_eq!(results[2].position.to_string(), "17:5");
+    }
+
+    #[test]
+    fn test_format_from_str() {
+        assert_eq!("summary".parse(), Ok(Format::Summary));
+        assert_eq!("junit".parse(), Ok(Format::JUnit));
+        assert_eq!("tap".parse(), Ok(Format::Tap));
+        assert_eq!(
+            "xml".parse::<Format>(),
+            Err(String::from(
+                "unknown report format xml, expected one of: summary, junit, tap"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_only() {
+        let input = r#"
+            test "uno" { assert.that(false) }
+            describe "grupo" {
+                only test "dos" { assert.that(true) }
+                test "tres" { assert.that(false) }
+            }
+        "#;
+
+        let results = run_source("only.wtest", input).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].full_name(), "grupo > dos");
+        assert_eq!(results[0].outcome, Outcome::Passed);
+    }
+
+    #[test]
+    fn test_file_errors() {
+        assert_eq!(
+            run_source("roto.wtest", "test \"roto\" { self }"),
+            Err(vec![String::from(
+                "self cannot be used at program top level"
+            )])
+        );
+        assert!(run_source("roto.wtest", "test \"roto\" {").is_err());
+    }
+
+    #[test]
+    fn test_discover_and_summary() {
+        let dir = std::env::temp_dir().join(format!("rollok-runner-{}", std::process::id()));
+        fs::create_dir_all(dir.join("aves")).unwrap();
+        fs::write(dir.join("aves").join("pepita.wtest"), AVES).unwrap();
+        fs::write(dir.join("ok.wtest"), "test \"ok\" { assert.that(true) }").unwrap();
+        fs::write(dir.join("notas.txt"), "no es un test").unwrap();
+
+        let files = discover(&dir).unwrap();
+        assert_eq!(
+            files,
+            vec![dir.join("aves").join("pepita.wtest"), dir.join("ok.wtest")]
+        );
+
+        let report = run(&dir).unwrap();
+        assert_eq!(
+            (report.passed(), report.failed(), report.errored()),
+            (3, 1, 1)
+        );
+        assert!(!report.success());
+
+        let mut out = vec![];
+        report.write_summary(&mut out).unwrap();
+        let summary = String::from_utf8(out).unwrap();
+        let pepita = dir.join("aves").join("pepita.wtest").display().to_string();
+        let expected = format!(
+            "✓ pepita > vuela
+✓ pepita > arranca con energia
+✗ pepita > se cansa
+! pepita > explota
+✓ ok
+
+Failures:
+
+1) pepita > se cansa [{pepita}:17:5]
+   AssertionException: expected 0 but found 100
+       at test \"se cansa\" [{pepita}:18:16]
+
+2) pepita > explota [{pepita}:21:5]
+   MessageNotUnderstoodException: pepita does not understand cantar/0
+       at test \"explota\" [{pepita}:22:16]
+
+5 tests: 3 passed, 1 failed, 1 errored
+"
+        );
+        assert_eq!(summary, expected);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_imports() {
+        let dir = std::env::temp_dir().join(format!("rollok-imports-{}", std::process::id()));
+        fs::create_dir_all(dir.join("aves")).unwrap();
+        fs::write(
+            dir.join("aves").join("pepita.wlk"),
+            "import comida.*\nobject pepita { method come() = alpiste.energia() }",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("aves").join("comida.wlk"),
+            "object alpiste { method energia() = 5 }",
+        )
+        .unwrap();
+
+        let test = dir.join("aves").join("pepita.wtest").display().to_string();
+        let results = run_source(
+            &test,
+            "import wollok.lang\nimport pepita.pepita\ntest \"come\" { assert.equals(5, pepita.come()) }",
+        )
+        .unwrap();
+        assert_eq!(results[0].outcome, Outcome::Passed);
+
+        let missing = dir.join("aves").join("nada.wlk").display().to_string();
+        let errors = run_source(&test, "import nada.*\ntest \"t\" { }").unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(
+            errors[0].starts_with(&format!("cannot import nada: {}: ", missing)),
+            "{}",
+            errors[0]
+        );
+
+        fs::write(dir.join("aves").join("rota.wlk"), "object rota {").unwrap();
+        let rota = dir.join("aves").join("rota.wlk").display().to_string();
+        let errors = run_source(&test, "import rota.*\ntest \"t\" { }").unwrap_err();
+        assert_eq!(errors[0], format!("{}: expected }}, got EOF instead", rota));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,86 @@
+use crate::runner::{Outcome, Report};
+use std::io;
+use std::io::Write;
+
+/// Writes the report as TAP version 13, with a YAML block describing each
+/// failure. Files that could not be run count as failed tests.
+pub fn write(report: &Report, out: &mut impl Write) -> io::Result<()> {
+    writeln!(out, "TAP version 13")?;
+    writeln!(
+        out,
+        "1..{}",
+        report.results.len() + report.file_errors.len()
+    )?;
+
+    let mut number = 0;
+    for result in &report.results {
+        number += 1;
+
+        let (severity, error) = match &result.outcome {
+            Outcome::Passed => {
+                writeln!(out, "ok {} - {}", number, result.full_name())?;
+                continue;
+            }
+            Outcome::Failed(error) => ("fail", error),
+            Outcome::Errored(error) => ("error", error),
+        };
+
+        writeln!(out, "not ok {} - {}", number, result.full_name())?;
+        writeln!(out, "  ---")?;
+        writeln!(out, "  message: {}", quote(&error.message))?;
+        writeln!(out, "  severity: {}", severity)?;
+        writeln!(
+            out,
+            "  at: {}",
+            quote(&format!("{}:{}", result.file, result.position))
+        )?;
+        if !error.stack_trace.is_empty() {
+            writeln!(out, "  stack: |")?;
+            for frame in &error.stack_trace {
+                writeln!(out, "    at {}", frame)?;
+            }
+        }
+        writeln!(out, "  ...")?;
+    }
+
+    for file_error in &report.file_errors {
+        number += 1;
+
+        writeln!(
+            out,
+            "not ok {} - {} could not be run",
+            number, file_error.file
+        )?;
+        writeln!(out, "  ---")?;
+        writeln!(out, "  message: {}", quote(&file_error.errors.join("\n")))?;
+        writeln!(out, "  severity: error")?;
+        writeln!(out, "  ...")?;
+    }
+
+    Ok(())
+}
+
+/// Quotes a value as a double-quoted YAML scalar.
+fn quote(text: &str) -> String {
+    let escaped = text
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{}\"", escaped)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::runner::{sample_report, Format};
+
+    #[test]
+    fn test_golden() {
+        let mut out = vec![];
+        sample_report().write(Format::Tap, &mut out).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            include_str!("testdata/report.tap")
+        );
+    }
+}
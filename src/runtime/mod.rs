@@ -0,0 +1,6 @@
+pub mod natives;
+pub mod object;
+pub mod pair;
+pub mod range;
+pub mod trace;
+pub mod value;
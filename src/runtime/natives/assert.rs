@@ -0,0 +1,238 @@
+use crate::evaluator::{EvalResult, Evaluator, Unwind};
+use crate::runtime::natives::{expect_boolean, expect_string, Natives};
+use crate::runtime::value::Value;
+
+pub fn register(natives: &mut Natives) {
+    natives.register("assert", "equals", 2, equals);
+    natives.register("assert", "notEquals", 2, not_equals);
+    natives.register("assert", "that", 1, that);
+    natives.register("assert", "notThat", 1, not_that);
+    natives.register("assert", "throwsException", 1, throws_exception);
+    natives.register(
+        "assert",
+        "throwsExceptionWithMessage",
+        2,
+        throws_exception_with_message,
+    );
+    natives.register("assert", "fail", 1, fail);
+}
+
+fn failure(evaluator: &mut Evaluator, message: String) -> Unwind {
+    evaluator.new_exception(
+        "AssertionException",
+        vec![(String::from("message"), Value::String(message))],
+    )
+}
+
+fn comparison_failure(
+    evaluator: &mut Evaluator,
+    message: String,
+    expected: Value,
+    actual: Value,
+) -> Unwind {
+    evaluator.new_exception(
+        "AssertionException",
+        vec![
+            (String::from("message"), Value::String(message)),
+            (String::from("expected"), expected),
+            (String::from("actual"), actual),
+        ],
+    )
+}
+
+fn same(evaluator: &mut Evaluator, expected: &Value, actual: &Value) -> Result<bool, Unwind> {
+    evaluator.equal(expected, actual)
+}
+
+fn equals(evaluator: &mut Evaluator, _: Value, arguments: Vec<Value>) -> EvalResult {
+    let (expected, actual) = (arguments[0].clone(), arguments[1].clone());
+    if same(evaluator, &expected, &actual)? {
+        return Ok(Value::Null);
+    }
+
+    let message = format!(
+        "expected {} but found {}",
+        evaluator.print_string(&expected)?,
+        evaluator.print_string(&actual)?
+    );
+    Err(comparison_failure(evaluator, message, expected, actual))
+}
+
+fn not_equals(evaluator: &mut Evaluator, _: Value, arguments: Vec<Value>) -> EvalResult {
+    let (expected, actual) = (arguments[0].clone(), arguments[1].clone());
+    if !same(evaluator, &expected, &actual)? {
+        return Ok(Value::Null);
+    }
+
+    let message = format!(
+        "expected something different from {}",
+        evaluator.print_string(&actual)?
+    );
+    Err(comparison_failure(evaluator, message, expected, actual))
+}
+
+fn that(evaluator: &mut Evaluator, _: Value, arguments: Vec<Value>) -> EvalResult {
+    match expect_boolean(&arguments[0])? {
+        true => Ok(Value::Null),
+        false => Err(failure(evaluator, String::from("value was not true"))),
+    }
+}
+
+fn not_that(evaluator: &mut Evaluator, _: Value, arguments: Vec<Value>) -> EvalResult {
+    match expect_boolean(&arguments[0])? {
+        false => Ok(Value::Null),
+        true => Err(failure(evaluator, String::from("value was not false"))),
+    }
+}
+
+/// Runs the block and hands back whatever it threw, or None if it finished.
+fn thrown(evaluator: &mut Evaluator, block: &Value) -> Result<Option<Value>, Unwind> {
+    match evaluator.apply(block, vec![]) {
+        Ok(_) => Ok(None),
+        Err(Unwind::Throw(exception)) => Ok(Some(exception)),
+        Err(Unwind::Error(message)) => Ok(Some(Value::String(message))),
+        Err(other) => Err(other),
+    }
+}
+
+fn throws_exception(evaluator: &mut Evaluator, _: Value, arguments: Vec<Value>) -> EvalResult {
+    match thrown(evaluator, &arguments[0])? {
+        Some(_) => Ok(Value::Null),
+        None => {
+            let message = format!("block {} should have failed", arguments[0]);
+            Err(failure(evaluator, message))
+        }
+    }
+}
+
+fn throws_exception_with_message(
+    evaluator: &mut Evaluator,
+    _: Value,
+    arguments: Vec<Value>,
+) -> EvalResult {
+    let expected = expect_string(&arguments[0])?;
+
+    let actual = match thrown(evaluator, &arguments[1])? {
+        Some(Value::Object(exception)) if exception.is_kind_of("Exception") => {
+            evaluator.send_message(Value::Object(exception), "message", vec![])?
+        }
+        Some(other) => other,
+        None => {
+            let message = format!("block {} should have failed", arguments[1]);
+            return Err(failure(evaluator, message));
+        }
+    };
+
+    if actual == Value::String(expected.clone()) {
+        return Ok(Value::Null);
+    }
+
+    let message = format!(
+        "expected exception message {} but found {}",
+        evaluator.print_string(&arguments[0])?,
+        evaluator.print_string(&actual)?
+    );
+    Err(comparison_failure(
+        evaluator,
+        message,
+        Value::String(expected),
+        actual,
+    ))
+}
+
+fn fail(evaluator: &mut Evaluator, _: Value, arguments: Vec<Value>) -> EvalResult {
+    let message = expect_string(&arguments[0])?;
+    Err(failure(evaluator, message))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::runtime::natives::check;
+
+    #[test]
+    fn test_equals() {
+        check(vec![
+            ("assert.equals(2, 1 + 1)", "null"),
+            ("assert.equals([1, 2], [1, 2])", "null"),
+            (
+                "assert.equals(\"a\", \"b\")",
+                "AssertionException: expected \"a\" but found \"b\"",
+            ),
+            (
+                "class Ave { } assert.equals(1, new Ave())",
+                "AssertionException: expected 1 but found an Ave",
+            ),
+            (
+                "object pepita { override method toString() = \"P\" } assert.equals([pepita], [])",
+                "AssertionException: expected [P] but found []",
+            ),
+            (
+                "try { assert.equals(1, 2) } catch e : AssertionException { [e.expected(), e.actual()] }",
+                "[1, 2]",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_not_equals() {
+        check(vec![
+            ("assert.notEquals(1, 2)", "null"),
+            (
+                "assert.notEquals(\"a\", \"a\")",
+                "AssertionException: expected something different from \"a\"",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_that() {
+        check(vec![
+            ("assert.that(1 < 2)", "null"),
+            (
+                "assert.that(false)",
+                "AssertionException: value was not true",
+            ),
+            ("assert.that(1)", "expected a boolean, got 1"),
+            ("assert.notThat(false)", "null"),
+            (
+                "assert.notThat(true)",
+                "AssertionException: value was not false",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_throws_exception() {
+        check(vec![
+            ("assert.throwsException({ throw new DomainException() })", "null"),
+            ("assert.throwsException({ 1 / 0 })", "null"),
+            (
+                "assert.throwsException({ 1 })",
+                "AssertionException: block { 1 } should have failed",
+            ),
+            (
+                "assert.throwsExceptionWithMessage(\"cansada\", { throw new DomainException(message = \"cansada\") })",
+                "null",
+            ),
+            (
+                "assert.throwsExceptionWithMessage(\"cansada\", { throw new DomainException(message = \"lejos\") })",
+                "AssertionException: expected exception message \"cansada\" but found \"lejos\"",
+            ),
+            (
+                "assert.throwsExceptionWithMessage(\"x\", { 1 })",
+                "AssertionException: block { 1 } should have failed",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_fail() {
+        check(vec![
+            ("assert.fail(\"nope\")", "AssertionException: nope"),
+            (
+                "try { assert.fail(\"nope\") } catch e : Exception { e.message() }",
+                "\"nope\"",
+            ),
+        ]);
+    }
+}
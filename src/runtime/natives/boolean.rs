@@ -0,0 +1,56 @@
+use crate::evaluator::{EvalResult, Evaluator};
+use crate::runtime::natives::{expect_boolean, Natives};
+use crate::runtime::value::Value;
+
+pub fn register(natives: &mut Natives) {
+    natives.register("Boolean", "and", 1, and);
+    natives.register("Boolean", "or", 1, or);
+    natives.register("Boolean", "negate", 0, negate);
+}
+
+fn and(_: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    let left = expect_boolean(&receiver)?;
+    let right = expect_boolean(&arguments[0])?;
+
+    Ok(Value::Boolean(left && right))
+}
+
+fn or(_: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    let left = expect_boolean(&receiver)?;
+    let right = expect_boolean(&arguments[0])?;
+
+    Ok(Value::Boolean(left || right))
+}
+
+fn negate(_: &mut Evaluator, receiver: Value, _: Vec<Value>) -> EvalResult {
+    Ok(Value::Boolean(!expect_boolean(&receiver)?))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::runtime::natives::check;
+
+    #[test]
+    fn test_and() {
+        check(vec![
+            ("true.and(true)", "true"),
+            ("true.and(false)", "false"),
+            ("false.and(true)", "false"),
+            ("true.and(1)", "expected a boolean, got 1"),
+        ]);
+    }
+
+    #[test]
+    fn test_or() {
+        check(vec![
+            ("false.or(true)", "true"),
+            ("false.or(false)", "false"),
+            ("true.or(false)", "true"),
+        ]);
+    }
+
+    #[test]
+    fn test_negate() {
+        check(vec![("true.negate()", "false"), ("false.negate()", "true")]);
+    }
+}
@@ -0,0 +1,699 @@
+use crate::evaluator::{EvalResult, Evaluator, Unwind};
+use crate::runtime::natives::{expect_boolean, expect_integer, NativeMethod, Natives};
+use crate::runtime::range::RangeIter;
+use crate::runtime::value::Value;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+const COLLECTIONS: [&str; 3] = ["List", "Set", "Range"];
+
+pub fn register(natives: &mut Natives) {
+    natives.register_constructor("List", || Value::List(Rc::new(RefCell::new(vec![]))));
+    natives.register_constructor("Set", || Value::Set(Rc::new(RefCell::new(vec![]))));
+
+    let methods: [(&str, usize, NativeMethod); 26] = [
+        ("size", 0, size),
+        ("isEmpty", 0, is_empty),
+        ("contains", 1, contains),
+        ("map", 1, map),
+        ("filter", 1, filter),
+        ("flatMap", 1, flat_map),
+        ("find", 1, find),
+        ("findOrElse", 2, find_or_else),
+        ("any", 1, any),
+        ("all", 1, all),
+        ("count", 1, count),
+        ("fold", 2, fold),
+        ("sum", 0, sum),
+        ("sum", 1, sum_by),
+        ("max", 0, max),
+        ("max", 1, max_by),
+        ("min", 0, min),
+        ("min", 1, min_by),
+        ("sortedBy", 1, sorted_by),
+        ("forEach", 1, for_each),
+        ("anyOne", 0, any_one),
+        ("asList", 0, as_list),
+        ("asSet", 0, as_set),
+        ("first", 0, first),
+        ("last", 0, last),
+        ("get", 1, get),
+    ];
+
+    for class in COLLECTIONS {
+        for (name, arity, method) in methods {
+            natives.register(class, name, arity, method);
+        }
+    }
+
+    for class in ["List", "Set"] {
+        natives.register(class, "add", 1, add);
+        natives.register(class, "addAll", 1, add_all);
+        natives.register(class, "remove", 1, remove);
+        natives.register(class, "clear", 0, clear);
+    }
+}
+
+/// The elements of a collection. Lists and sets are copied, so blocks may
+/// modify them while they are walked; ranges are walked without building
+/// their elements up front.
+pub(super) enum Elements {
+    Values(std::vec::IntoIter<Value>),
+    Range(RangeIter),
+}
+
+impl Iterator for Elements {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Value> {
+        match self {
+            Elements::Values(values) => values.next(),
+            Elements::Range(numbers) => numbers.next().map(|number| Value::Number(number as f64)),
+        }
+    }
+}
+
+pub(super) fn elements(collection: &Value) -> Result<Elements, Unwind> {
+    match collection {
+        Value::List(elements) | Value::Set(elements) => {
+            Ok(Elements::Values(elements.borrow().clone().into_iter()))
+        }
+        Value::Range(range) => Ok(Elements::Range(range.iter())),
+        other => Err(Unwind::Error(format!(
+            "expected a collection, got {}",
+            other
+        ))),
+    }
+}
+
+/// Builds a collection of the receiver's kind: sets stay sets, lists and
+/// ranges become lists.
+fn same_kind(evaluator: &mut Evaluator, receiver: &Value, elements: Vec<Value>) -> EvalResult {
+    match receiver {
+        Value::Set(_) => new_set(evaluator, elements),
+        _ => Ok(Value::List(Rc::new(RefCell::new(elements)))),
+    }
+}
+
+fn new_set(evaluator: &mut Evaluator, elements: Vec<Value>) -> EvalResult {
+    let mut unique: Vec<Value> = vec![];
+
+    for element in elements {
+        if position(evaluator, &unique, &element)?.is_none() {
+            unique.push(element);
+        }
+    }
+
+    Ok(Value::Set(Rc::new(RefCell::new(unique))))
+}
+
+/// Where `element` is among `values`, comparing with == so that objects
+/// that override equals are found. Callers pass a copy of their elements,
+/// since equals may modify the collection being searched.
+pub(super) fn position(
+    evaluator: &mut Evaluator,
+    values: &[Value],
+    element: &Value,
+) -> Result<Option<usize>, Unwind> {
+    for (index, value) in values.iter().enumerate() {
+        if evaluator.equal(value, element)? {
+            return Ok(Some(index));
+        }
+    }
+
+    Ok(None)
+}
+
+/// The first element and the rest, failing on empty collections.
+fn expect_non_empty(receiver: &Value) -> Result<(Value, Elements), Unwind> {
+    let mut elements = elements(receiver)?;
+
+    match elements.next() {
+        Some(first) => Ok((first, elements)),
+        None => Err(Unwind::Error(format!("{} is empty", receiver))),
+    }
+}
+
+fn test(evaluator: &mut Evaluator, predicate: &Value, element: &Value) -> Result<bool, Unwind> {
+    let result = evaluator.apply(predicate, vec![element.clone()])?;
+    expect_boolean(&result)
+}
+
+fn less_than(evaluator: &mut Evaluator, left: &Value, right: &Value) -> Result<bool, Unwind> {
+    let result = evaluator.eval_infix_operator("<", left.clone(), right.clone())?;
+    expect_boolean(&result)
+}
+
+fn size(_: &mut Evaluator, receiver: Value, _: Vec<Value>) -> EvalResult {
+    Ok(Value::Number(elements(&receiver)?.count() as f64))
+}
+
+fn is_empty(_: &mut Evaluator, receiver: Value, _: Vec<Value>) -> EvalResult {
+    Ok(Value::Boolean(elements(&receiver)?.next().is_none()))
+}
+
+fn contains(evaluator: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    for element in elements(&receiver)? {
+        if evaluator.equal(&element, &arguments[0])? {
+            return Ok(Value::Boolean(true));
+        }
+    }
+
+    Ok(Value::Boolean(false))
+}
+
+fn map(evaluator: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    let mut mapped = vec![];
+
+    for element in elements(&receiver)? {
+        mapped.push(evaluator.apply(&arguments[0], vec![element])?);
+    }
+
+    same_kind(evaluator, &receiver, mapped)
+}
+
+fn filter(evaluator: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    let mut filtered = vec![];
+
+    for element in elements(&receiver)? {
+        if test(evaluator, &arguments[0], &element)? {
+            filtered.push(element);
+        }
+    }
+
+    same_kind(evaluator, &receiver, filtered)
+}
+
+fn flat_map(evaluator: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    let mut flattened = vec![];
+
+    for element in elements(&receiver)? {
+        let mapped = evaluator.apply(&arguments[0], vec![element])?;
+        flattened.extend(elements(&mapped)?);
+    }
+
+    same_kind(evaluator, &receiver, flattened)
+}
+
+fn find(evaluator: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    for element in elements(&receiver)? {
+        if test(evaluator, &arguments[0], &element)? {
+            return Ok(element);
+        }
+    }
+
+    Err(Unwind::Error(format!("no element of {} matches", receiver)))
+}
+
+fn find_or_else(evaluator: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    for element in elements(&receiver)? {
+        if test(evaluator, &arguments[0], &element)? {
+            return Ok(element);
+        }
+    }
+
+    evaluator.apply(&arguments[1], vec![])
+}
+
+fn any(evaluator: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    for element in elements(&receiver)? {
+        if test(evaluator, &arguments[0], &element)? {
+            return Ok(Value::Boolean(true));
+        }
+    }
+
+    Ok(Value::Boolean(false))
+}
+
+fn all(evaluator: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    for element in elements(&receiver)? {
+        if !test(evaluator, &arguments[0], &element)? {
+            return Ok(Value::Boolean(false));
+        }
+    }
+
+    Ok(Value::Boolean(true))
+}
+
+fn count(evaluator: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    let mut count = 0;
+
+    for element in elements(&receiver)? {
+        if test(evaluator, &arguments[0], &element)? {
+            count += 1;
+        }
+    }
+
+    Ok(Value::Number(count as f64))
+}
+
+fn fold(evaluator: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    let mut accumulator = arguments[0].clone();
+
+    for element in elements(&receiver)? {
+        accumulator = evaluator.apply(&arguments[1], vec![accumulator, element])?;
+    }
+
+    Ok(accumulator)
+}
+
+fn sum(evaluator: &mut Evaluator, receiver: Value, _: Vec<Value>) -> EvalResult {
+    let mut total = Value::Number(0.0);
+
+    for element in elements(&receiver)? {
+        total = evaluator.eval_infix_operator("+", total, element)?;
+    }
+
+    Ok(total)
+}
+
+fn sum_by(evaluator: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    let mut total = Value::Number(0.0);
+
+    for element in elements(&receiver)? {
+        let value = evaluator.apply(&arguments[0], vec![element])?;
+        total = evaluator.eval_infix_operator("+", total, value)?;
+    }
+
+    Ok(total)
+}
+
+fn max(evaluator: &mut Evaluator, receiver: Value, _: Vec<Value>) -> EvalResult {
+    let (mut max, elements) = expect_non_empty(&receiver)?;
+
+    for element in elements {
+        if less_than(evaluator, &max, &element)? {
+            max = element;
+        }
+    }
+
+    Ok(max)
+}
+
+fn max_by(evaluator: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    let (mut max, elements) = expect_non_empty(&receiver)?;
+    let mut max_value = evaluator.apply(&arguments[0], vec![max.clone()])?;
+
+    for element in elements {
+        let value = evaluator.apply(&arguments[0], vec![element.clone()])?;
+        if less_than(evaluator, &max_value, &value)? {
+            max = element;
+            max_value = value;
+        }
+    }
+
+    Ok(max)
+}
+
+fn min(evaluator: &mut Evaluator, receiver: Value, _: Vec<Value>) -> EvalResult {
+    let (mut min, elements) = expect_non_empty(&receiver)?;
+
+    for element in elements {
+        if less_than(evaluator, &element, &min)? {
+            min = element;
+        }
+    }
+
+    Ok(min)
+}
+
+fn min_by(evaluator: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    let (mut min, elements) = expect_non_empty(&receiver)?;
+    let mut min_value = evaluator.apply(&arguments[0], vec![min.clone()])?;
+
+    for element in elements {
+        let value = evaluator.apply(&arguments[0], vec![element.clone()])?;
+        if less_than(evaluator, &value, &min_value)? {
+            min = element;
+            min_value = value;
+        }
+    }
+
+    Ok(min)
+}
+
+fn sorted_by(evaluator: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    let mut sorted: Vec<Value> = vec![];
+
+    // Insertion sort: the comparison is a Wollok closure that may fail, which
+    // rules out slice::sort_by.
+    for element in elements(&receiver)? {
+        let mut index = sorted.len();
+        while index > 0 {
+            let before = evaluator.apply(
+                &arguments[0],
+                vec![element.clone(), sorted[index - 1].clone()],
+            )?;
+            if !expect_boolean(&before)? {
+                break;
+            }
+            index -= 1;
+        }
+        sorted.insert(index, element);
+    }
+
+    Ok(Value::List(Rc::new(RefCell::new(sorted))))
+}
+
+fn for_each(evaluator: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    for element in elements(&receiver)? {
+        evaluator.apply(&arguments[0], vec![element])?;
+    }
+
+    Ok(Value::Null)
+}
+
+fn any_one(_: &mut Evaluator, receiver: Value, _: Vec<Value>) -> EvalResult {
+    Ok(expect_non_empty(&receiver)?.0)
+}
+
+fn as_list(_: &mut Evaluator, receiver: Value, _: Vec<Value>) -> EvalResult {
+    Ok(Value::List(Rc::new(RefCell::new(
+        elements(&receiver)?.collect(),
+    ))))
+}
+
+fn as_set(evaluator: &mut Evaluator, receiver: Value, _: Vec<Value>) -> EvalResult {
+    new_set(evaluator, elements(&receiver)?.collect())
+}
+
+fn first(_: &mut Evaluator, receiver: Value, _: Vec<Value>) -> EvalResult {
+    Ok(expect_non_empty(&receiver)?.0)
+}
+
+fn last(_: &mut Evaluator, receiver: Value, _: Vec<Value>) -> EvalResult {
+    let (first, rest) = expect_non_empty(&receiver)?;
+    Ok(rest.last().unwrap_or(first))
+}
+
+fn get(_: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    let mut elements = elements(&receiver)?;
+    let index = expect_integer(&arguments[0])?;
+
+    match usize::try_from(index)
+        .ok()
+        .and_then(|index| elements.nth(index))
+    {
+        Some(element) => Ok(element),
+        None => Err(Unwind::Error(format!(
+            "index {} out of bounds for {}",
+            index, receiver
+        ))),
+    }
+}
+
+fn add(evaluator: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    let element = arguments[0].clone();
+
+    match &receiver {
+        Value::List(elements) => elements.borrow_mut().push(element),
+        Value::Set(elements) => {
+            let known = elements.borrow().clone();
+            if position(evaluator, &known, &element)?.is_none() {
+                elements.borrow_mut().push(element);
+            }
+        }
+        other => return Err(Unwind::Error(format!("{} cannot be modified", other))),
+    }
+
+    Ok(Value::Null)
+}
+
+fn add_all(evaluator: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    for element in elements(&arguments[0])? {
+        add(evaluator, receiver.clone(), vec![element])?;
+    }
+
+    Ok(Value::Null)
+}
+
+fn remove(evaluator: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    match &receiver {
+        Value::List(elements) | Value::Set(elements) => {
+            let known = elements.borrow().clone();
+            if let Some(index) = position(evaluator, &known, &arguments[0])? {
+                // equals may have shrunk the collection in the meantime.
+                let mut elements = elements.borrow_mut();
+                if index < elements.len() {
+                    elements.remove(index);
+                }
+            }
+            Ok(Value::Null)
+        }
+        other => Err(Unwind::Error(format!("{} cannot be modified", other))),
+    }
+}
+
+fn clear(_: &mut Evaluator, receiver: Value, _: Vec<Value>) -> EvalResult {
+    match &receiver {
+        Value::List(elements) | Value::Set(elements) => {
+            elements.borrow_mut().clear();
+            Ok(Value::Null)
+        }
+        other => Err(Unwind::Error(format!("{} cannot be modified", other))),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::runtime::natives::check;
+
+    #[test]
+    fn test_constructors() {
+        check(vec![
+            ("new List()", "[]"),
+            ("new Set()", "#{}"),
+            ("new List(size = 1)", "List has no fields to initialize"),
+        ]);
+    }
+
+    #[test]
+    fn test_size_and_is_empty() {
+        check(vec![
+            ("[1, 2, 3].size()", "3"),
+            ("#{1, 1}.size()", "1"),
+            ("(1..4).size()", "4"),
+            ("[].isEmpty()", "true"),
+            ("#{1}.isEmpty()", "false"),
+        ]);
+    }
+
+    #[test]
+    fn test_contains() {
+        check(vec![
+            ("[1, 2].contains(2)", "true"),
+            ("#{\"a\"}.contains(\"b\")", "false"),
+            ("(1..5).contains(3)", "true"),
+        ]);
+    }
+
+    #[test]
+    fn test_user_equality() {
+        check(vec![
+            (
+                "class P { const x method x() = x override method equals(o) = x == o.x() } [new P(x = 1)].contains(new P(x = 1))",
+                "true",
+            ),
+            (
+                "class P { const x method x() = x override method equals(o) = x == o.x() } #{new P(x = 1), new P(x = 1)}.size()",
+                "1",
+            ),
+            (
+                "class P { const x method x() = x override method equals(o) = x == o.x() } const s = #{new P(x = 1)} s.add(new P(x = 1)) s.size()",
+                "1",
+            ),
+            (
+                "class P { const x method x() = x override method equals(o) = x == o.x() } [new P(x = 1), new P(x = 1)].asSet().size()",
+                "1",
+            ),
+            (
+                "class P { const x method x() = x override method equals(o) = x == o.x() } const l = [new P(x = 1)] l.remove(new P(x = 1)) l.isEmpty()",
+                "true",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_add_and_remove() {
+        check(vec![
+            ("const l = [1] l.add(2) l.add(2) l", "[1, 2, 2]"),
+            ("const s = #{1} s.add(2) s.add(2) s", "#{1, 2}"),
+            ("const l = [1, 2, 1] l.remove(1) l", "[2, 1]"),
+            ("const s = #{1, 2} s.remove(3) s", "#{1, 2}"),
+            ("const l = [1] l.addAll(#{2, 3}) l", "[1, 2, 3]"),
+            ("const l = [1, 2] l.clear() l", "[]"),
+            ("const l = [] l.add(l) l.contains(l)", "true"),
+            ("const l = [] l.add(l) l.remove(l) l", "[]"),
+            ("const s = #{} s.add(s) s.add(s) s.size()", "1"),
+            (
+                "(1..2).add(3)",
+                "MessageNotUnderstoodException: 1..2 does not understand add/1",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_map() {
+        check(vec![
+            ("[1, 2].map({ x => x * 10 })", "[10, 20]"),
+            ("#{1, 2}.map({ x => 0 })", "#{0}"),
+            ("(1..3).map({ x => x * x })", "[1, 4, 9]"),
+            (
+                "[1].map({ x, y => x })",
+                "closure { x, y => x } expects 2 arguments, got 1",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_filter() {
+        check(vec![
+            ("[1, 2, 3, 4].filter({ x => x.even() })", "[2, 4]"),
+            ("#{1, 2, 3}.filter({ x => x > 1 })", "#{2, 3}"),
+            ("[1].filter({ x => x })", "expected a boolean, got 1"),
+        ]);
+    }
+
+    #[test]
+    fn test_flat_map() {
+        check(vec![
+            ("[1, 2].flatMap({ x => [x, x] })", "[1, 1, 2, 2]"),
+            ("[1, 3].flatMap({ x => 1..x })", "[1, 1, 2, 3]"),
+        ]);
+    }
+
+    #[test]
+    fn test_find() {
+        check(vec![
+            ("[1, 2, 3].find({ x => x > 1 })", "2"),
+            ("[1].find({ x => x > 1 })", "no element of [1] matches"),
+            ("[1, 2].findOrElse({ x => x > 1 }, { 0 })", "2"),
+            ("[1].findOrElse({ x => x > 1 }, { 0 })", "0"),
+        ]);
+    }
+
+    #[test]
+    fn test_any_and_all() {
+        check(vec![
+            ("[1, 2].any({ x => x > 1 })", "true"),
+            ("[].any({ x => true })", "false"),
+            ("[1, 2].all({ x => x > 1 })", "false"),
+            ("[].all({ x => false })", "true"),
+        ]);
+    }
+
+    #[test]
+    fn test_huge_ranges() {
+        // Materialising any of these ranges would take gigabytes.
+        check(vec![
+            ("(1..100000000000).any({ n => n > 2 })", "true"),
+            ("(1..100000000000).all({ n => n < 3 })", "false"),
+            ("(1..100000000000).find({ n => n.even() })", "2"),
+            ("(1..100000000000).first()", "1"),
+            ("(1..100000000000).get(4)", "5"),
+            ("(1..100000000000).isEmpty()", "false"),
+            ("(1..100000000000).contains(3)", "true"),
+        ]);
+    }
+
+    #[test]
+    fn test_count() {
+        check(vec![("(1..10).count({ x => x.odd() })", "5")]);
+    }
+
+    #[test]
+    fn test_fold() {
+        check(vec![
+            ("[1, 2, 3].fold(0, { acc, x => acc + x })", "6"),
+            ("[\"a\", \"b\"].fold(\"\", { acc, x => acc + x })", "\"ab\""),
+        ]);
+    }
+
+    #[test]
+    fn test_sum() {
+        check(vec![
+            ("[1, 2, 3].sum()", "6"),
+            ("[].sum()", "0"),
+            ("(1..4).sum()", "10"),
+            ("[\"a\"].sum({ x => x.size() })", "1"),
+        ]);
+    }
+
+    #[test]
+    fn test_max_and_min() {
+        check(vec![
+            ("[3, 7, 2].max()", "7"),
+            ("[3, 7, 2].min()", "2"),
+            ("[\"b\", \"a\"].min()", "\"a\""),
+            ("[\"aa\", \"b\"].max({ s => s.size() })", "\"aa\""),
+            ("[\"aa\", \"b\"].min({ s => s.size() })", "\"b\""),
+            ("[].max()", "[] is empty"),
+        ]);
+    }
+
+    #[test]
+    fn test_sorted_by() {
+        check(vec![
+            ("[3, 1, 2].sortedBy({ a, b => a < b })", "[1, 2, 3]"),
+            ("#{3, 1, 2}.sortedBy({ a, b => a > b })", "[3, 2, 1]"),
+            (
+                "[\"bb\", \"a\", \"cc\"].sortedBy({ a, b => a.size() < b.size() })",
+                "[\"a\", \"bb\", \"cc\"]",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_for_each() {
+        check(vec![
+            (
+                "var total = 0 [1, 2].forEach({ x => total = total + x }) total",
+                "3",
+            ),
+            (
+                "var total = 0 (1..3).forEach({ x => total = total + x }) total",
+                "6",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_any_one() {
+        check(vec![("#{5}.anyOne()", "5"), ("[].anyOne()", "[] is empty")]);
+    }
+
+    #[test]
+    fn test_conversions() {
+        check(vec![
+            ("[1, 1, 2].asSet()", "#{1, 2}"),
+            ("#{1, 2}.asList()", "[1, 2]"),
+            ("(1..3).asList()", "[1, 2, 3]"),
+        ]);
+    }
+
+    #[test]
+    fn test_positional_access() {
+        check(vec![
+            ("[1, 2, 3].first()", "1"),
+            ("[1, 2, 3].last()", "3"),
+            ("[1, 2, 3].get(1)", "2"),
+            ("[1].get(3)", "index 3 out of bounds for [1]"),
+        ]);
+    }
+
+    #[test]
+    fn test_chained_pipeline() {
+        check(vec![(
+            r#"
+                class Ave {
+                    const energia
+                    method puedeVolar() = energia > 10
+                    method energia() = energia
+                }
+                const aves = [new Ave(energia = 5), new Ave(energia = 20), new Ave(energia = 30)]
+                aves.filter({ a => a.puedeVolar() }).map({ a => a.energia() }).sum()
+            "#,
+            "50",
+        )]);
+    }
+}
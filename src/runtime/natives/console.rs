@@ -0,0 +1,53 @@
+use crate::evaluator::{EvalResult, Evaluator, Unwind};
+use crate::runtime::natives::{expect_string, Natives};
+use crate::runtime::value::Value;
+
+pub fn register(natives: &mut Natives) {
+    natives.register("console", "println", 1, println);
+}
+
+fn println(evaluator: &mut Evaluator, _: Value, arguments: Vec<Value>) -> EvalResult {
+    let text = evaluator.send_message(arguments[0].clone(), "toString", vec![])?;
+    let text = expect_string(&text)?;
+
+    writeln!(evaluator.output(), "{}", text)
+        .map_err(|e| Unwind::Error(format!("could not write to the console: {}", e)))?;
+    Ok(Value::Null)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::evaluator::Evaluator;
+    use crate::lexer2::Lexer;
+    use crate::parser::Parser;
+
+    fn output(input: &str, program: Option<&str>) -> String {
+        let mut parser = Parser::new(Lexer::new(input));
+        let program_node = parser.parse_program().unwrap();
+
+        let mut out = vec![];
+        let mut evaluator = Evaluator::with_output(&mut out);
+        evaluator.run_program(&program_node, program).unwrap();
+        drop(evaluator);
+
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_println() {
+        assert_eq!(
+            output(
+                "program saludo { console.println(\"hola\") }",
+                Some("saludo")
+            ),
+            "hola\n"
+        );
+        assert_eq!(
+            output(
+                "object pepita { override method toString() = \"pepa\" }\nprogram main { console.println(pepita) console.println(1 + 1) }",
+                None
+            ),
+            "pepa\n2\n"
+        );
+    }
+}
@@ -0,0 +1,279 @@
+use crate::evaluator::{EvalResult, Evaluator, Unwind};
+use crate::runtime::natives::collections::position;
+use crate::runtime::natives::Natives;
+use crate::runtime::value::Value;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+type Entries = Rc<RefCell<Vec<(Value, Value)>>>;
+
+pub fn register(natives: &mut Natives) {
+    natives.register_constructor("Dictionary", || {
+        Value::Dictionary(Rc::new(RefCell::new(vec![])))
+    });
+
+    natives.register("Dictionary", "put", 2, put);
+    natives.register("Dictionary", "get", 1, get);
+    natives.register("Dictionary", "basicGet", 1, basic_get);
+    natives.register("Dictionary", "getOrElse", 2, get_or_else);
+    natives.register("Dictionary", "containsKey", 1, contains_key);
+    natives.register("Dictionary", "remove", 1, remove);
+    natives.register("Dictionary", "keys", 0, keys);
+    natives.register("Dictionary", "values", 0, values);
+    natives.register("Dictionary", "forEach", 1, for_each);
+    natives.register("Dictionary", "size", 0, size);
+    natives.register("Dictionary", "isEmpty", 0, is_empty);
+    natives.register("Dictionary", "clear", 0, clear);
+}
+
+fn entries(receiver: &Value) -> Result<Entries, Unwind> {
+    match receiver {
+        Value::Dictionary(entries) => Ok(Rc::clone(entries)),
+        other => Err(Unwind::Error(format!(
+            "expected a dictionary, got {}",
+            other
+        ))),
+    }
+}
+
+/// Where `key` is among the entries, compared with == on a copy of the
+/// keys, since equals may look at the dictionary itself.
+fn key_position(
+    evaluator: &mut Evaluator,
+    entries: &Entries,
+    key: &Value,
+) -> Result<Option<usize>, Unwind> {
+    let keys: Vec<Value> = entries
+        .borrow()
+        .iter()
+        .map(|(entry_key, _)| entry_key.clone())
+        .collect();
+
+    position(evaluator, &keys, key)
+}
+
+pub(super) fn lookup(
+    evaluator: &mut Evaluator,
+    receiver: &Value,
+    key: &Value,
+) -> Result<Option<Value>, Unwind> {
+    let entries = entries(receiver)?;
+    let index = key_position(evaluator, &entries, key)?;
+
+    Ok(index.and_then(|index| entries.borrow().get(index).map(|(_, value)| value.clone())))
+}
+
+fn put(evaluator: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    let entries = entries(&receiver)?;
+    let mut arguments = arguments.into_iter();
+    let (key, value) = (arguments.next().unwrap(), arguments.next().unwrap());
+
+    let index = key_position(evaluator, &entries, &key)?;
+    let mut entries = entries.borrow_mut();
+    match index.and_then(|index| entries.get_mut(index)) {
+        Some(entry) => entry.1 = value,
+        None => entries.push((key, value)),
+    }
+
+    Ok(Value::Null)
+}
+
+fn get(evaluator: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    match lookup(evaluator, &receiver, &arguments[0])? {
+        Some(value) => Ok(value),
+        None => Err(Unwind::Error(format!("key {} not found", arguments[0]))),
+    }
+}
+
+fn basic_get(evaluator: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    Ok(lookup(evaluator, &receiver, &arguments[0])?.unwrap_or(Value::Null))
+}
+
+fn get_or_else(evaluator: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    match lookup(evaluator, &receiver, &arguments[0])? {
+        Some(value) => Ok(value),
+        None => evaluator.apply(&arguments[1], vec![]),
+    }
+}
+
+fn contains_key(evaluator: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    let entries = entries(&receiver)?;
+    let index = key_position(evaluator, &entries, &arguments[0])?;
+
+    Ok(Value::Boolean(index.is_some()))
+}
+
+fn remove(evaluator: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    let entries = entries(&receiver)?;
+    if let Some(index) = key_position(evaluator, &entries, &arguments[0])? {
+        let mut entries = entries.borrow_mut();
+        if index < entries.len() {
+            entries.remove(index);
+        }
+    }
+
+    Ok(Value::Null)
+}
+
+fn keys(_: &mut Evaluator, receiver: Value, _: Vec<Value>) -> EvalResult {
+    let entries = entries(&receiver)?;
+    let keys = entries
+        .borrow()
+        .iter()
+        .map(|(key, _)| key.clone())
+        .collect();
+
+    Ok(Value::List(Rc::new(RefCell::new(keys))))
+}
+
+fn values(_: &mut Evaluator, receiver: Value, _: Vec<Value>) -> EvalResult {
+    let entries = entries(&receiver)?;
+    let values = entries
+        .borrow()
+        .iter()
+        .map(|(_, value)| value.clone())
+        .collect();
+
+    Ok(Value::List(Rc::new(RefCell::new(values))))
+}
+
+fn for_each(evaluator: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    // Iterate over a snapshot so the closure can modify the dictionary.
+    let snapshot = entries(&receiver)?.borrow().clone();
+
+    for (key, value) in snapshot {
+        evaluator.apply(&arguments[0], vec![key, value])?;
+    }
+
+    Ok(Value::Null)
+}
+
+fn size(_: &mut Evaluator, receiver: Value, _: Vec<Value>) -> EvalResult {
+    Ok(Value::Number(entries(&receiver)?.borrow().len() as f64))
+}
+
+fn is_empty(_: &mut Evaluator, receiver: Value, _: Vec<Value>) -> EvalResult {
+    Ok(Value::Boolean(entries(&receiver)?.borrow().is_empty()))
+}
+
+fn clear(_: &mut Evaluator, receiver: Value, _: Vec<Value>) -> EvalResult {
+    entries(&receiver)?.borrow_mut().clear();
+
+    Ok(Value::Null)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::runtime::natives::check;
+
+    #[test]
+    fn test_put_and_get() {
+        check(vec![
+            (
+                "const d = new Dictionary() d.put(\"a\", 1) d.get(\"a\")",
+                "1",
+            ),
+            (
+                "const d = new Dictionary() d.put(\"a\", 1) d.put(\"a\", 2) d",
+                "a Dictionary [\"a\" -> 2]",
+            ),
+            ("new Dictionary().get(\"x\")", "key \"x\" not found"),
+        ]);
+    }
+
+    #[test]
+    fn test_basic_get() {
+        check(vec![
+            ("new Dictionary().basicGet(1)", "null"),
+            ("const d = new Dictionary() d.put(1, 2) d.basicGet(1)", "2"),
+        ]);
+    }
+
+    #[test]
+    fn test_get_or_else() {
+        check(vec![
+            ("new Dictionary().getOrElse(1, { 0 })", "0"),
+            (
+                "const d = new Dictionary() d.put(1, 2) d.getOrElse(1, { 0 })",
+                "2",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_contains_key_and_remove() {
+        check(vec![
+            (
+                "const d = new Dictionary() d.put(1, 2) d.containsKey(1)",
+                "true",
+            ),
+            (
+                "const d = new Dictionary() d.put(1, 2) d.remove(1) d.containsKey(1)",
+                "false",
+            ),
+            (
+                "const d = new Dictionary() d.put(d, 1) d.put(d, 2) d.size()",
+                "1",
+            ),
+            (
+                "const d = new Dictionary() d.put(d, 1) d.remove(d) d.isEmpty()",
+                "true",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_user_equality() {
+        check(vec![
+            (
+                "class P { const x method x() = x override method equals(o) = x == o.x() } const d = new Dictionary() d.put(new P(x = 1), 1) d.put(new P(x = 1), 2) d.size()",
+                "1",
+            ),
+            (
+                "class P { const x method x() = x override method equals(o) = x == o.x() } const d = new Dictionary() d.put(new P(x = 1), 1) d.get(new P(x = 1))",
+                "1",
+            ),
+            (
+                "class P { const x method x() = x override method equals(o) = x == o.x() } const d = new Dictionary() d.put(new P(x = 1), 1) d.remove(new P(x = 1)) d.isEmpty()",
+                "true",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_keys_and_values() {
+        check(vec![
+            (
+                "const d = new Dictionary() d.put(\"a\", 1) d.put(\"b\", 2) d.keys()",
+                "[\"a\", \"b\"]",
+            ),
+            (
+                "const d = new Dictionary() d.put(\"a\", 1) d.put(\"b\", 2) d.values()",
+                "[1, 2]",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_for_each() {
+        check(vec![(
+            "const d = new Dictionary() d.put(\"a\", 1) d.put(\"b\", 2) var s = \"\" d.forEach({ k, v => s = s + k + v }) s",
+            "\"a1b2\"",
+        )]);
+    }
+
+    #[test]
+    fn test_size_is_empty_and_clear() {
+        check(vec![
+            ("new Dictionary().isEmpty()", "true"),
+            (
+                "const d = new Dictionary() d.put(1, 1) d.put(2, 2) d.size()",
+                "2",
+            ),
+            (
+                "const d = new Dictionary() d.put(1, 1) d.clear() d.isEmpty()",
+                "true",
+            ),
+        ]);
+    }
+}
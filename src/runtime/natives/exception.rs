@@ -0,0 +1,68 @@
+use crate::evaluator::{EvalResult, Evaluator, Unwind};
+use crate::runtime::natives::Natives;
+use crate::runtime::trace::StackFrame;
+use crate::runtime::value::Value;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+pub fn register(natives: &mut Natives) {
+    natives.register("Exception", "getStackTrace", 0, get_stack_trace);
+    natives.register(
+        "Exception",
+        "getStackTraceAsString",
+        0,
+        get_stack_trace_as_string,
+    );
+}
+
+fn stack_trace(receiver: &Value) -> Result<Vec<StackFrame>, Unwind> {
+    match receiver {
+        Value::Object(object) => Ok(object.stack_trace.borrow().clone()),
+        other => Err(Unwind::Error(format!(
+            "expected an exception, got {}",
+            other
+        ))),
+    }
+}
+
+fn get_stack_trace(_: &mut Evaluator, receiver: Value, _: Vec<Value>) -> EvalResult {
+    let frames = stack_trace(&receiver)?
+        .iter()
+        .map(|frame| Value::String(frame.to_string()))
+        .collect();
+
+    Ok(Value::List(Rc::new(RefCell::new(frames))))
+}
+
+fn get_stack_trace_as_string(_: &mut Evaluator, receiver: Value, _: Vec<Value>) -> EvalResult {
+    let lines: Vec<String> = stack_trace(&receiver)?
+        .iter()
+        .map(|frame| format!("at {}", frame))
+        .collect();
+
+    Ok(Value::String(lines.join("\n")))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::runtime::natives::check;
+
+    #[test]
+    fn test_get_stack_trace() {
+        check(vec![
+            (
+                "object a { method m() = new Exception() }\na.m().getStackTrace()",
+                "[\"a.m() [<input>:1:25]\", \"top level [<input>:2:3]\"]",
+            ),
+            ("new Exception().getStackTrace().size()", "1"),
+        ]);
+    }
+
+    #[test]
+    fn test_get_stack_trace_as_string() {
+        check(vec![(
+            "object a { method m(x) = new Exception() }\na.m(1).getStackTraceAsString()",
+            "\"at a.m(x) [<input>:1:26]\\nat top level [<input>:2:3]\"",
+        )]);
+    }
+}
@@ -0,0 +1,130 @@
+mod assert;
+mod boolean;
+mod collections;
+mod console;
+mod dictionary;
+mod exception;
+mod number;
+mod object;
+mod pair;
+mod range;
+mod string;
+
+use crate::evaluator::{EvalResult, Evaluator, Unwind};
+use crate::runtime::value::Value;
+use std::collections::HashMap;
+
+pub type NativeMethod = fn(&mut Evaluator, Value, Vec<Value>) -> EvalResult;
+pub type NativeConstructor = fn() -> Value;
+
+#[derive(Default)]
+pub struct Natives {
+    methods: HashMap<(&'static str, &'static str, usize), NativeMethod>,
+    constructors: HashMap<&'static str, NativeConstructor>,
+}
+
+impl Natives {
+    pub fn new() -> Self {
+        let mut natives = Self::default();
+
+        object::register(&mut natives);
+        assert::register(&mut natives);
+        console::register(&mut natives);
+        number::register(&mut natives);
+        string::register(&mut natives);
+        boolean::register(&mut natives);
+        collections::register(&mut natives);
+        range::register(&mut natives);
+        pair::register(&mut natives);
+        dictionary::register(&mut natives);
+        exception::register(&mut natives);
+
+        natives
+    }
+
+    pub fn register(
+        &mut self,
+        class: &'static str,
+        name: &'static str,
+        arity: usize,
+        method: NativeMethod,
+    ) {
+        self.methods.insert((class, name, arity), method);
+    }
+
+    pub fn register_constructor(&mut self, class: &'static str, constructor: NativeConstructor) {
+        self.constructors.insert(class, constructor);
+    }
+
+    pub fn lookup(&self, class: &str, name: &str, arity: usize) -> Option<NativeMethod> {
+        self.methods.get(&(class, name, arity)).copied()
+    }
+
+    /// Names of the native methods of a class, in no particular order.
+    pub fn names(&self, class: &str) -> Vec<&'static str> {
+        self.methods
+            .keys()
+            .filter(|(owner, _, _)| *owner == class)
+            .map(|(_, name, _)| *name)
+            .collect()
+    }
+
+    pub fn constructor(&self, class: &str) -> Option<NativeConstructor> {
+        self.constructors.get(class).copied()
+    }
+}
+
+fn expect_number(value: &Value) -> Result<f64, Unwind> {
+    match value {
+        Value::Number(number) => Ok(*number),
+        other => Err(Unwind::Error(format!("expected a number, got {}", other))),
+    }
+}
+
+fn expect_integer(value: &Value) -> Result<i64, Unwind> {
+    match expect_number(value)? {
+        number if number.fract() == 0.0 => Ok(number as i64),
+        number => Err(Unwind::Error(format!(
+            "expected an integer, got {}",
+            Value::Number(number)
+        ))),
+    }
+}
+
+fn expect_string(value: &Value) -> Result<String, Unwind> {
+    match value {
+        Value::String(string) => Ok(string.clone()),
+        other => Err(Unwind::Error(format!("expected a string, got {}", other))),
+    }
+}
+
+fn expect_boolean(value: &Value) -> Result<bool, Unwind> {
+    match value {
+        Value::Boolean(boolean) => Ok(*boolean),
+        other => Err(Unwind::Error(format!("expected a boolean, got {}", other))),
+    }
+}
+
+#[cfg(test)]
+fn check(tests: Vec<(&str, &str)>) {
+    use crate::lexer2::Lexer;
+    use crate::parser::Parser;
+
+    for (input, expected) in tests {
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program().unwrap();
+        assert!(
+            parser.errors().is_empty(),
+            "parser errors for {}: {:?}",
+            input,
+            parser.errors()
+        );
+
+        let result = match Evaluator::new().eval_program(&program) {
+            Ok(value) => value.to_string(),
+            Err(err) => err.message,
+        };
+        assert_eq!(result, expected, "input: {}", input);
+    }
+}
@@ -0,0 +1,265 @@
+use crate::evaluator::{EvalResult, Evaluator, Unwind};
+use crate::runtime::natives::{expect_integer, expect_number, Natives};
+use crate::runtime::value::Value;
+
+pub fn register(natives: &mut Natives) {
+    natives.register("Number", "between", 2, between);
+    natives.register("Number", "max", 1, max);
+    natives.register("Number", "min", 1, min);
+    natives.register("Number", "abs", 0, abs);
+    natives.register("Number", "even", 0, even);
+    natives.register("Number", "odd", 0, odd);
+    natives.register("Number", "roundUp", 0, round_up);
+    natives.register("Number", "roundUp", 1, round_up_decimals);
+    natives.register("Number", "truncate", 1, truncate);
+    natives.register("Number", "square", 0, square);
+    natives.register("Number", "squareRoot", 0, square_root);
+    natives.register("Number", "div", 1, div);
+    natives.register("Number", "rem", 1, rem);
+    natives.register("Number", "isInteger", 0, is_integer);
+    natives.register("Number", "times", 1, times);
+}
+
+fn between(_: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    let number = expect_number(&receiver)?;
+    let min = expect_number(&arguments[0])?;
+    let max = expect_number(&arguments[1])?;
+
+    Ok(Value::Boolean(min <= number && number <= max))
+}
+
+fn max(_: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    let number = expect_number(&receiver)?;
+    let other = expect_number(&arguments[0])?;
+
+    Ok(Value::Number(number.max(other)))
+}
+
+fn min(_: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    let number = expect_number(&receiver)?;
+    let other = expect_number(&arguments[0])?;
+
+    Ok(Value::Number(number.min(other)))
+}
+
+fn abs(_: &mut Evaluator, receiver: Value, _: Vec<Value>) -> EvalResult {
+    Ok(Value::Number(expect_number(&receiver)?.abs()))
+}
+
+fn even(_: &mut Evaluator, receiver: Value, _: Vec<Value>) -> EvalResult {
+    Ok(Value::Boolean(expect_integer(&receiver)? % 2 == 0))
+}
+
+fn odd(_: &mut Evaluator, receiver: Value, _: Vec<Value>) -> EvalResult {
+    Ok(Value::Boolean(expect_integer(&receiver)? % 2 != 0))
+}
+
+fn round_up(_: &mut Evaluator, receiver: Value, _: Vec<Value>) -> EvalResult {
+    Ok(Value::Number(expect_number(&receiver)?.ceil()))
+}
+
+fn round_up_decimals(_: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    let number = expect_number(&receiver)?;
+    let factor = decimals_factor(&arguments[0])?;
+
+    Ok(Value::Number((number * factor).ceil() / factor))
+}
+
+fn truncate(_: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    let number = expect_number(&receiver)?;
+    let factor = decimals_factor(&arguments[0])?;
+
+    Ok(Value::Number((number * factor).trunc() / factor))
+}
+
+fn decimals_factor(decimals: &Value) -> Result<f64, Unwind> {
+    match expect_integer(decimals)? {
+        decimals if decimals < 0 => Err(Unwind::Error(format!(
+            "decimals must be positive, got {}",
+            decimals
+        ))),
+        decimals => Ok(10f64.powi(decimals as i32)),
+    }
+}
+
+fn square(_: &mut Evaluator, receiver: Value, _: Vec<Value>) -> EvalResult {
+    let number = expect_number(&receiver)?;
+
+    Ok(Value::Number(number * number))
+}
+
+fn square_root(_: &mut Evaluator, receiver: Value, _: Vec<Value>) -> EvalResult {
+    match expect_number(&receiver)? {
+        number if number < 0.0 => Err(Unwind::Error(format!(
+            "cannot take the square root of {}",
+            receiver
+        ))),
+        number => Ok(Value::Number(number.sqrt())),
+    }
+}
+
+fn div(_: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    let number = expect_integer(&receiver)?;
+
+    match expect_integer(&arguments[0])? {
+        0 => Err(Unwind::Error(String::from("division by zero"))),
+        other => match number.checked_div(other) {
+            Some(result) => Ok(Value::Number(result as f64)),
+            None => Err(Unwind::Error(format!(
+                "{}.div({}) does not fit in an integer",
+                number, other
+            ))),
+        },
+    }
+}
+
+fn rem(_: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    let number = expect_integer(&receiver)?;
+
+    match expect_integer(&arguments[0])? {
+        0 => Err(Unwind::Error(String::from("division by zero"))),
+        other => match number.checked_rem(other) {
+            Some(result) => Ok(Value::Number(result as f64)),
+            None => Err(Unwind::Error(format!(
+                "{}.rem({}) does not fit in an integer",
+                number, other
+            ))),
+        },
+    }
+}
+
+fn is_integer(_: &mut Evaluator, receiver: Value, _: Vec<Value>) -> EvalResult {
+    Ok(Value::Boolean(expect_number(&receiver)?.fract() == 0.0))
+}
+
+fn times(evaluator: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    let times = expect_integer(&receiver)?;
+
+    for index in 1..=times {
+        evaluator.apply(&arguments[0], vec![Value::Number(index as f64)])?;
+    }
+
+    Ok(Value::Null)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::runtime::natives::check;
+
+    #[test]
+    fn test_between() {
+        check(vec![
+            ("5.between(1, 10)", "true"),
+            ("1.between(1, 1)", "true"),
+            ("0.5.between(1, 10)", "false"),
+            ("5.between(\"a\", 10)", "expected a number, got \"a\""),
+        ]);
+    }
+
+    #[test]
+    fn test_max() {
+        check(vec![("3.max(7)", "7"), ("-1.max(-5)", "-1")]);
+    }
+
+    #[test]
+    fn test_min() {
+        check(vec![("3.min(7)", "3"), ("2.5.min(2)", "2")]);
+    }
+
+    #[test]
+    fn test_abs() {
+        check(vec![("(-3).abs()", "3"), ("2.5.abs()", "2.5")]);
+    }
+
+    #[test]
+    fn test_even() {
+        check(vec![
+            ("4.even()", "true"),
+            ("3.even()", "false"),
+            ("1.5.even()", "expected an integer, got 1.5"),
+        ]);
+    }
+
+    #[test]
+    fn test_odd() {
+        check(vec![("3.odd()", "true"), ("(-2).odd()", "false")]);
+    }
+
+    #[test]
+    fn test_round_up() {
+        check(vec![
+            ("2.1.roundUp()", "3"),
+            ("(-2.5).roundUp()", "-2"),
+            ("3.14159.roundUp(2)", "3.15"),
+            ("1.roundUp(-1)", "decimals must be positive, got -1"),
+        ]);
+    }
+
+    #[test]
+    fn test_truncate() {
+        check(vec![
+            ("3.14159.truncate(3)", "3.141"),
+            ("7.9.truncate(0)", "7"),
+        ]);
+    }
+
+    #[test]
+    fn test_square() {
+        check(vec![("4.square()", "16"), ("1.5.square()", "2.25")]);
+    }
+
+    #[test]
+    fn test_square_root() {
+        check(vec![
+            ("16.squareRoot()", "4"),
+            ("(-4).squareRoot()", "cannot take the square root of -4"),
+        ]);
+    }
+
+    #[test]
+    fn test_div() {
+        check(vec![
+            ("7.div(2)", "3"),
+            ("7.div(0)", "division by zero"),
+            (
+                "(0 - 9223372036854775807 - 1).div(-1)",
+                "-9223372036854775808.div(-1) does not fit in an integer",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_rem() {
+        check(vec![
+            ("7.rem(2)", "1"),
+            ("9.rem(3)", "0"),
+            (
+                "(0 - 9223372036854775807 - 1).rem(-1)",
+                "-9223372036854775808.rem(-1) does not fit in an integer",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_is_integer() {
+        check(vec![
+            ("7.isInteger()", "true"),
+            ("0.5.isInteger()", "false"),
+        ]);
+    }
+
+    #[test]
+    fn test_times() {
+        check(vec![
+            (
+                "var total = 0 3.times({ i => total = total + i }) total",
+                "6",
+            ),
+            (
+                "var calls = 0 0.times({ i => calls = calls + 1 }) calls",
+                "0",
+            ),
+            ("2.times(1)", "expected a closure, got 1"),
+        ]);
+    }
+}
@@ -0,0 +1,249 @@
+use crate::evaluator::{EvalResult, Evaluator, Unwind};
+use crate::runtime::natives::collections::position;
+use crate::runtime::natives::dictionary::lookup;
+use crate::runtime::natives::{expect_boolean, Natives};
+use crate::runtime::value::Value;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+pub fn register(natives: &mut Natives) {
+    natives.register("Object", "==", 1, equal);
+    natives.register("Object", "!=", 1, not_equal);
+    natives.register("Object", "equals", 1, equals);
+    natives.register("Object", "identity", 0, identity);
+    natives.register("Object", "toString", 0, to_string);
+    natives.register("Object", "printString", 0, print_string);
+}
+
+fn equal(evaluator: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    evaluator.send_message(receiver, "equals", arguments)
+}
+
+fn not_equal(evaluator: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    let equal = evaluator.send_message(receiver, "equals", arguments)?;
+    Ok(Value::Boolean(!expect_boolean(&equal)?))
+}
+
+/// Collections and pairs are equal when their elements are, compared with
+/// == so that elements that override equals are compared their way.
+fn equals(evaluator: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    let equal = match (&receiver, &arguments[0]) {
+        (Value::List(left), Value::List(right)) if !Rc::ptr_eq(left, right) => {
+            let (left, right) = (left.borrow().clone(), right.borrow().clone());
+            left.len() == right.len() && all_equal(evaluator, &left, &right)?
+        }
+        (Value::Set(left), Value::Set(right)) if !Rc::ptr_eq(left, right) => {
+            let (left, right) = (left.borrow().clone(), right.borrow().clone());
+            left.len() == right.len() && contains_all(evaluator, &right, &left)?
+        }
+        (Value::Dictionary(left), Value::Dictionary(right)) if !Rc::ptr_eq(left, right) => {
+            let entries = left.borrow().clone();
+            entries.len() == right.borrow().len()
+                && same_entries(evaluator, &entries, &arguments[0])?
+        }
+        (Value::Pair(left), Value::Pair(right)) => {
+            evaluator.equal(left.x(), right.x())? && evaluator.equal(left.y(), right.y())?
+        }
+        (left, right) => left == right,
+    };
+
+    Ok(Value::Boolean(equal))
+}
+
+fn all_equal(evaluator: &mut Evaluator, left: &[Value], right: &[Value]) -> Result<bool, Unwind> {
+    for (left, right) in left.iter().zip(right) {
+        if !evaluator.equal(left, right)? {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+fn contains_all(
+    evaluator: &mut Evaluator,
+    values: &[Value],
+    elements: &[Value],
+) -> Result<bool, Unwind> {
+    for element in elements {
+        if position(evaluator, values, element)?.is_none() {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+fn same_entries(
+    evaluator: &mut Evaluator,
+    entries: &[(Value, Value)],
+    other: &Value,
+) -> Result<bool, Unwind> {
+    for (key, value) in entries {
+        match lookup(evaluator, other, key)? {
+            Some(other_value) if evaluator.equal(value, &other_value)? => {}
+            _ => return Ok(false),
+        }
+    }
+
+    Ok(true)
+}
+
+fn identity(_: &mut Evaluator, receiver: Value, _: Vec<Value>) -> EvalResult {
+    let identity = match &receiver {
+        Value::List(elements) | Value::Set(elements) => Rc::as_ptr(elements) as usize as u64,
+        Value::Dictionary(entries) => Rc::as_ptr(entries) as usize as u64,
+        Value::Pair(pair) => Rc::as_ptr(pair) as usize as u64,
+        Value::Object(object) => Rc::as_ptr(object) as usize as u64,
+        Value::Module(module) => Rc::as_ptr(module) as usize as u64,
+        Value::Closure(closure) => Rc::as_ptr(closure) as usize as u64,
+        other => {
+            let mut hasher = DefaultHasher::new();
+            other.type_name().hash(&mut hasher);
+            other.to_string().hash(&mut hasher);
+            hasher.finish()
+        }
+    };
+
+    Ok(Value::Number((identity % (1 << 53)) as f64))
+}
+
+/// Collections print their elements with printString, so elements that
+/// override toString show up the way they chose.
+fn to_string(evaluator: &mut Evaluator, receiver: Value, _: Vec<Value>) -> EvalResult {
+    let string = match &receiver {
+        Value::List(elements) => {
+            let elements = elements.borrow().clone();
+            format!("[{}]", print_all(evaluator, &elements)?)
+        }
+        Value::Set(elements) => {
+            let elements = elements.borrow().clone();
+            format!("#{{{}}}", print_all(evaluator, &elements)?)
+        }
+        Value::Dictionary(entries) => {
+            let mut printed = vec![];
+            for (key, value) in entries.borrow().clone() {
+                printed.push(format!(
+                    "{} -> {}",
+                    evaluator.print_string(&key)?,
+                    evaluator.print_string(&value)?
+                ));
+            }
+            format!("a Dictionary [{}]", printed.join(", "))
+        }
+        Value::Pair(pair) => format!(
+            "{} -> {}",
+            evaluator.print_string(pair.x())?,
+            evaluator.print_string(pair.y())?
+        ),
+        other => other.to_string(),
+    };
+
+    Ok(Value::String(string))
+}
+
+fn print_all(evaluator: &mut Evaluator, elements: &[Value]) -> Result<String, Unwind> {
+    let mut printed = vec![];
+    for element in elements {
+        printed.push(evaluator.print_string(element)?);
+    }
+
+    Ok(printed.join(", "))
+}
+
+fn print_string(evaluator: &mut Evaluator, receiver: Value, _: Vec<Value>) -> EvalResult {
+    evaluator.send_message(receiver, "toString", vec![])
+}
+
+#[cfg(test)]
+mod test {
+    use crate::runtime::natives::check;
+
+    #[test]
+    fn test_equal() {
+        check(vec![
+            ("object a { } a == a", "true"),
+            ("object a { } object b { } a == b", "false"),
+            (
+                "class P { const x method x() = x method equals(o) = x == o.x() } new P(x = 1) == new P(x = 1)",
+                "true",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_not_equal() {
+        check(vec![
+            ("object a { } object b { } a != b", "true"),
+            (
+                "class P { method equals(o) = true } new P() != new P()",
+                "false",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_equals() {
+        check(vec![
+            ("1.equals(1)", "true"),
+            ("\"a\".equals(\"b\")", "false"),
+            ("[1, 2].equals([1, 2])", "true"),
+            ("object a { } a.equals(a)", "true"),
+            ("#{1, 2}.equals(#{2, 1})", "true"),
+            ("(1 -> [2]).equals(1 -> [2])", "true"),
+            ("[1, 2].equals([1, 3])", "false"),
+            (
+                "class P { const x method x() = x override method equals(o) = x == o.x() } [new P(x = 1)] == [new P(x = 1)]",
+                "true",
+            ),
+            (
+                "class P { const x method x() = x override method equals(o) = x == o.x() } const d = new Dictionary() d.put(1, new P(x = 1)) const e = new Dictionary() e.put(1, new P(x = 1)) d == e",
+                "true",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_identity() {
+        check(vec![
+            ("object a { } a.identity() == a.identity()", "true"),
+            (
+                "object a { } object b { } a.identity() == b.identity()",
+                "false",
+            ),
+            ("\"hola\".identity() == \"hola\".identity()", "true"),
+            ("1.identity() == \"1\".identity()", "false"),
+        ]);
+    }
+
+    #[test]
+    fn test_to_string() {
+        check(vec![
+            ("object pepita { } pepita.toString()", "\"pepita\""),
+            ("class Ave { } new Ave().toString()", "\"an Ave\""),
+            ("[1, \"a\"].toString()", "\"[1, \\\"a\\\"]\""),
+            ("null.toString()", "\"null\""),
+            (
+                "object pepita { override method toString() = \"P\" } [pepita, #{pepita}].toString()",
+                "\"[P, #{P}]\"",
+            ),
+            (
+                "object pepita { override method toString() = \"P\" } const d = new Dictionary() d.put(pepita, pepita -> 1) d.printString()",
+                "\"a Dictionary [P -> P -> 1]\"",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_print_string() {
+        check(vec![
+            ("object pepita { } pepita.printString()", "\"pepita\""),
+            (
+                "object pepita { override method toString() = \"pepa\" } pepita.printString()",
+                "\"pepa\"",
+            ),
+            ("true.printString()", "\"true\""),
+        ]);
+    }
+}
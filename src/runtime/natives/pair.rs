@@ -0,0 +1,43 @@
+use crate::evaluator::{EvalResult, Evaluator, Unwind};
+use crate::runtime::natives::Natives;
+use crate::runtime::pair::Pair;
+use crate::runtime::value::Value;
+use std::rc::Rc;
+
+pub fn register(natives: &mut Natives) {
+    natives.register("Pair", "x", 0, x);
+    natives.register("Pair", "y", 0, y);
+    natives.register("Pair", "key", 0, x);
+    natives.register("Pair", "value", 0, y);
+}
+
+fn expect_pair(value: &Value) -> Result<Rc<Pair<Value>>, Unwind> {
+    match value {
+        Value::Pair(pair) => Ok(Rc::clone(pair)),
+        other => Err(Unwind::Error(format!("expected a pair, got {}", other))),
+    }
+}
+
+fn x(_: &mut Evaluator, receiver: Value, _: Vec<Value>) -> EvalResult {
+    Ok(expect_pair(&receiver)?.x().clone())
+}
+
+fn y(_: &mut Evaluator, receiver: Value, _: Vec<Value>) -> EvalResult {
+    Ok(expect_pair(&receiver)?.y().clone())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::runtime::natives::check;
+
+    #[test]
+    fn test_accessors() {
+        check(vec![
+            ("(\"a\" -> 1).x()", "\"a\""),
+            ("(\"a\" -> 1).y()", "1"),
+            ("(\"a\" -> 1).key()", "\"a\""),
+            ("(\"a\" -> 1).value()", "1"),
+            ("(1 -> 2 -> 3).key().value()", "2"),
+        ]);
+    }
+}
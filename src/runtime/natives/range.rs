@@ -0,0 +1,81 @@
+use crate::evaluator::{EvalResult, Evaluator, Unwind};
+use crate::runtime::natives::{expect_boolean, expect_integer, Natives};
+use crate::runtime::range::Range;
+use crate::runtime::value::Value;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Registered after the collection natives, replacing their generic sum,
+/// map and filter for ranges.
+pub fn register(natives: &mut Natives) {
+    natives.register("Range", "step", 1, step);
+    natives.register("Range", "sum", 0, sum);
+    natives.register("Range", "map", 1, map);
+    natives.register("Range", "filter", 1, filter);
+}
+
+fn expect_range(value: &Value) -> Result<Range, Unwind> {
+    match value {
+        Value::Range(range) => Ok(*range),
+        other => Err(Unwind::Error(format!("expected a range, got {}", other))),
+    }
+}
+
+fn step(_: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    let range = expect_range(&receiver)?;
+    let step = expect_integer(&arguments[0])?;
+
+    range.step(step).map(Value::Range).map_err(Unwind::Error)
+}
+
+fn sum(_: &mut Evaluator, receiver: Value, _: Vec<Value>) -> EvalResult {
+    match expect_range(&receiver)?.sum() {
+        Some(sum) => Ok(Value::Number(sum as f64)),
+        None => Err(Unwind::Error(format!(
+            "the sum of {} does not fit in an integer",
+            receiver
+        ))),
+    }
+}
+
+fn map(evaluator: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    let mapped = expect_range(&receiver)?
+        .map(|n| evaluator.apply(&arguments[0], vec![Value::Number(n as f64)]))?;
+
+    Ok(Value::List(Rc::new(RefCell::new(mapped))))
+}
+
+fn filter(evaluator: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    let kept = expect_range(&receiver)?.filter(|n| {
+        let result = evaluator.apply(&arguments[0], vec![Value::Number(n as f64)])?;
+        expect_boolean(&result)
+    })?;
+
+    let kept = kept.into_iter().map(|n| Value::Number(n as f64)).collect();
+    Ok(Value::List(Rc::new(RefCell::new(kept))))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::runtime::natives::check;
+
+    #[test]
+    fn test_step() {
+        check(vec![
+            ("(1..10).step(3).asList()", "[1, 4, 7, 10]"),
+            ("(10..1).step(-4).map({ n => n })", "[10, 6, 2]"),
+            ("(1..10).step(0)", "range step cannot be zero"),
+        ]);
+    }
+
+    #[test]
+    fn test_sum_map_and_filter() {
+        check(vec![
+            ("(1..10).sum()", "55"),
+            ("(1..10).step(2).sum()", "25"),
+            ("(1..4).map({ n => n * 2 })", "[2, 4, 6, 8]"),
+            ("(1..6).filter({ n => n.odd() })", "[1, 3, 5]"),
+            ("(1..3).filter({ n => n })", "expected a boolean, got 1"),
+        ]);
+    }
+}
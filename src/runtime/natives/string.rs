@@ -0,0 +1,309 @@
+use crate::evaluator::{EvalResult, Evaluator, Unwind};
+use crate::runtime::natives::{expect_integer, expect_string, Natives};
+use crate::runtime::value::Value;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+pub fn register(natives: &mut Natives) {
+    natives.register("String", "size", 0, size);
+    natives.register("String", "length", 0, size);
+    natives.register("String", "isEmpty", 0, is_empty);
+    natives.register("String", "toUpperCase", 0, to_upper_case);
+    natives.register("String", "toLowerCase", 0, to_lower_case);
+    natives.register("String", "contains", 1, contains);
+    natives.register("String", "startsWith", 1, starts_with);
+    natives.register("String", "endsWith", 1, ends_with);
+    natives.register("String", "split", 1, split);
+    natives.register("String", "trim", 0, trim);
+    natives.register("String", "reverse", 0, reverse);
+    natives.register("String", "take", 1, take);
+    natives.register("String", "drop", 1, drop);
+    natives.register("String", "charAt", 1, char_at);
+    natives.register("String", "+", 1, concat);
+    natives.register("String", "<", 1, less_than);
+    natives.register("String", ">", 1, greater_than);
+    natives.register("String", "<=", 1, less_eq);
+    natives.register("String", ">=", 1, greater_eq);
+    natives.register("String", "toString", 0, to_string);
+    natives.register("String", "printString", 0, print_string);
+}
+
+fn size(_: &mut Evaluator, receiver: Value, _: Vec<Value>) -> EvalResult {
+    let string = expect_string(&receiver)?;
+
+    Ok(Value::Number(string.chars().count() as f64))
+}
+
+fn is_empty(_: &mut Evaluator, receiver: Value, _: Vec<Value>) -> EvalResult {
+    Ok(Value::Boolean(expect_string(&receiver)?.is_empty()))
+}
+
+fn to_upper_case(_: &mut Evaluator, receiver: Value, _: Vec<Value>) -> EvalResult {
+    Ok(Value::String(expect_string(&receiver)?.to_uppercase()))
+}
+
+fn to_lower_case(_: &mut Evaluator, receiver: Value, _: Vec<Value>) -> EvalResult {
+    Ok(Value::String(expect_string(&receiver)?.to_lowercase()))
+}
+
+fn contains(_: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    let string = expect_string(&receiver)?;
+    let other = expect_string(&arguments[0])?;
+
+    Ok(Value::Boolean(string.contains(other.as_str())))
+}
+
+fn starts_with(_: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    let string = expect_string(&receiver)?;
+    let prefix = expect_string(&arguments[0])?;
+
+    Ok(Value::Boolean(string.starts_with(prefix.as_str())))
+}
+
+fn ends_with(_: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    let string = expect_string(&receiver)?;
+    let suffix = expect_string(&arguments[0])?;
+
+    Ok(Value::Boolean(string.ends_with(suffix.as_str())))
+}
+
+fn split(_: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    let string = expect_string(&receiver)?;
+    let separator = expect_string(&arguments[0])?;
+
+    if separator.is_empty() {
+        return Err(Unwind::Error(String::from("separator cannot be empty")));
+    }
+
+    let parts = string
+        .split(separator.as_str())
+        .map(|part| Value::String(String::from(part)))
+        .collect();
+
+    Ok(Value::List(Rc::new(RefCell::new(parts))))
+}
+
+fn trim(_: &mut Evaluator, receiver: Value, _: Vec<Value>) -> EvalResult {
+    Ok(Value::String(String::from(
+        expect_string(&receiver)?.trim(),
+    )))
+}
+
+fn reverse(_: &mut Evaluator, receiver: Value, _: Vec<Value>) -> EvalResult {
+    Ok(Value::String(
+        expect_string(&receiver)?.chars().rev().collect(),
+    ))
+}
+
+fn take(_: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    let string = expect_string(&receiver)?;
+    let count = expect_count(&arguments[0])?;
+
+    Ok(Value::String(string.chars().take(count).collect()))
+}
+
+fn drop(_: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    let string = expect_string(&receiver)?;
+    let count = expect_count(&arguments[0])?;
+
+    Ok(Value::String(string.chars().skip(count).collect()))
+}
+
+fn char_at(_: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    let string = expect_string(&receiver)?;
+    let index = expect_count(&arguments[0])?;
+
+    match string.chars().nth(index) {
+        Some(c) => Ok(Value::String(c.to_string())),
+        None => Err(Unwind::Error(format!(
+            "index {} out of bounds for {}",
+            index, receiver
+        ))),
+    }
+}
+
+fn expect_count(value: &Value) -> Result<usize, Unwind> {
+    match expect_integer(value)? {
+        count if count < 0 => Err(Unwind::Error(format!(
+            "expected a positive number, got {}",
+            count
+        ))),
+        count => Ok(count as usize),
+    }
+}
+
+fn concat(evaluator: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    let string = expect_string(&receiver)?;
+    let other = match &arguments[0] {
+        Value::String(other) => other.clone(),
+        other => evaluator.print_string(other)?,
+    };
+
+    Ok(Value::String(string + other.as_str()))
+}
+
+fn compare(receiver: &Value, arguments: &[Value]) -> Result<std::cmp::Ordering, Unwind> {
+    let string = expect_string(receiver)?;
+    let other = expect_string(&arguments[0])?;
+
+    Ok(string.cmp(&other))
+}
+
+fn less_than(_: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    Ok(Value::Boolean(compare(&receiver, &arguments)?.is_lt()))
+}
+
+fn greater_than(_: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    Ok(Value::Boolean(compare(&receiver, &arguments)?.is_gt()))
+}
+
+fn less_eq(_: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    Ok(Value::Boolean(compare(&receiver, &arguments)?.is_le()))
+}
+
+fn greater_eq(_: &mut Evaluator, receiver: Value, arguments: Vec<Value>) -> EvalResult {
+    Ok(Value::Boolean(compare(&receiver, &arguments)?.is_ge()))
+}
+
+fn to_string(_: &mut Evaluator, receiver: Value, _: Vec<Value>) -> EvalResult {
+    Ok(Value::String(expect_string(&receiver)?))
+}
+
+fn print_string(_: &mut Evaluator, receiver: Value, _: Vec<Value>) -> EvalResult {
+    Ok(Value::String(receiver.to_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::runtime::natives::check;
+
+    #[test]
+    fn test_size() {
+        check(vec![
+            ("\"hola\".size()", "4"),
+            ("\"\".size()", "0"),
+            ("\"año\".length()", "3"),
+        ]);
+    }
+
+    #[test]
+    fn test_is_empty() {
+        check(vec![
+            ("\"\".isEmpty()", "true"),
+            ("\"a\".isEmpty()", "false"),
+        ]);
+    }
+
+    #[test]
+    fn test_to_upper_case() {
+        check(vec![("\"Hola\".toUpperCase()", "\"HOLA\"")]);
+    }
+
+    #[test]
+    fn test_to_lower_case() {
+        check(vec![("\"Hola\".toLowerCase()", "\"hola\"")]);
+    }
+
+    #[test]
+    fn test_contains() {
+        check(vec![
+            ("\"pepita\".contains(\"pit\")", "true"),
+            ("\"pepita\".contains(\"x\")", "false"),
+            ("\"pepita\".contains(1)", "expected a string, got 1"),
+        ]);
+    }
+
+    #[test]
+    fn test_starts_with() {
+        check(vec![
+            ("\"pepita\".startsWith(\"pe\")", "true"),
+            ("\"pepita\".startsWith(\"ta\")", "false"),
+        ]);
+    }
+
+    #[test]
+    fn test_ends_with() {
+        check(vec![
+            ("\"pepita\".endsWith(\"ta\")", "true"),
+            ("\"pepita\".endsWith(\"pe\")", "false"),
+        ]);
+    }
+
+    #[test]
+    fn test_split() {
+        check(vec![
+            ("\"a,b,c\".split(\",\")", "[\"a\", \"b\", \"c\"]"),
+            ("\"abc\".split(\",\")", "[\"abc\"]"),
+            ("\"abc\".split(\"\")", "separator cannot be empty"),
+        ]);
+    }
+
+    #[test]
+    fn test_trim() {
+        check(vec![("\"  hola \".trim()", "\"hola\"")]);
+    }
+
+    #[test]
+    fn test_reverse() {
+        check(vec![("\"hola\".reverse()", "\"aloh\"")]);
+    }
+
+    #[test]
+    fn test_take() {
+        check(vec![
+            ("\"pepita\".take(3)", "\"pep\""),
+            ("\"pe\".take(5)", "\"pe\""),
+            ("\"pe\".take(-1)", "expected a positive number, got -1"),
+        ]);
+    }
+
+    #[test]
+    fn test_drop() {
+        check(vec![
+            ("\"pepita\".drop(2)", "\"pita\""),
+            ("\"pe\".drop(5)", "\"\""),
+        ]);
+    }
+
+    #[test]
+    fn test_char_at() {
+        check(vec![
+            ("\"hola\".charAt(1)", "\"o\""),
+            ("\"hola\".charAt(4)", "index 4 out of bounds for \"hola\""),
+        ]);
+    }
+
+    #[test]
+    fn test_concat() {
+        check(vec![
+            ("\"a\" + \"b\"", "\"ab\""),
+            ("\"n = \" + 1", "\"n = 1\""),
+            ("\"l = \" + [1, \"a\"]", "\"l = [1, \\\"a\\\"]\""),
+            (
+                "object pepita { override method toString() = \"pepa\" } \"soy \" + pepita",
+                "\"soy pepa\"",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_comparisons() {
+        check(vec![
+            ("\"a\" < \"b\"", "true"),
+            ("\"b\" > \"a\"", "true"),
+            ("\"a\" <= \"a\"", "true"),
+            ("\"a\" >= \"b\"", "false"),
+            ("\"a\" < 1", "expected a string, got 1"),
+        ]);
+    }
+
+    #[test]
+    fn test_to_string() {
+        check(vec![("\"hola\".toString()", "\"hola\"")]);
+    }
+
+    #[test]
+    fn test_print_string() {
+        check(vec![("\"hola\".printString()", "\"\\\"hola\\\"\"")]);
+    }
+}
@@ -0,0 +1,244 @@
+use crate::ast::{MethodDeclaration, VarStatement};
+use crate::evaluator::environment::Environment;
+use crate::runtime::trace::StackFrame;
+use std::cell::RefCell;
+use std::fmt::{Debug, Formatter};
+use std::rc::Rc;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ModuleKind {
+    Class,
+    Mixin,
+    Object,
+}
+
+pub struct Module {
+    pub kind: ModuleKind,
+    pub name: Option<String>,
+    pub parent: Option<Rc<Module>>,
+    pub mixins: Vec<Rc<Module>>,
+    pub fields: Vec<VarStatement>,
+    pub methods: Vec<MethodDeclaration>,
+    pub env: Rc<RefCell<Environment>>,
+    pub file: String,
+}
+
+impl Module {
+    pub fn name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => String::from("Object"),
+        }
+    }
+
+    /// The module itself, then its mixins from last to first, each followed
+    /// by the mixins it is mixed with, and then its parent's linearization.
+    /// A mixin reached twice keeps its first, most specific, place.
+    pub fn linearization(self: &Rc<Self>) -> Vec<Rc<Module>> {
+        let mut modules = vec![Rc::clone(self)];
+
+        let mut supertypes: Vec<&Rc<Module>> = self.mixins.iter().rev().collect();
+        supertypes.extend(&self.parent);
+
+        for supertype in supertypes {
+            for module in supertype.linearization() {
+                if !modules.iter().any(|known| Rc::ptr_eq(known, &module)) {
+                    modules.push(module);
+                }
+            }
+        }
+
+        modules
+    }
+
+    pub fn lookup(
+        self: &Rc<Self>,
+        name: &str,
+        arity: usize,
+        from: usize,
+    ) -> Option<(usize, MethodDeclaration)> {
+        self.linearization()
+            .iter()
+            .enumerate()
+            .skip(from)
+            .find_map(|(level, module)| {
+                module
+                    .methods
+                    .iter()
+                    .find(|method| method.name.value == name && method.parameters.len() == arity)
+                    .map(|method| (level, method.clone()))
+            })
+    }
+
+    pub fn inherits_from(self: &Rc<Self>, name: &str) -> bool {
+        self.linearization()
+            .iter()
+            .any(|module| module.name.as_deref() == Some(name))
+    }
+}
+
+impl Debug for Module {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}({})", self.kind, self.name())
+    }
+}
+
+pub struct Object {
+    pub module: Rc<Module>,
+    pub fields: Rc<RefCell<Environment>>,
+    /// Captured when an exception is instantiated, empty for other objects.
+    pub stack_trace: RefCell<Vec<StackFrame>>,
+}
+
+impl Object {
+    pub fn new(module: Rc<Module>) -> Self {
+        let fields = Environment::new_enclosed(Rc::clone(&module.env));
+
+        Self {
+            module,
+            fields: Rc::new(RefCell::new(fields)),
+            stack_trace: RefCell::new(vec![]),
+        }
+    }
+
+    pub fn lookup(&self, name: &str, arity: usize) -> Option<(usize, MethodDeclaration)> {
+        self.module.lookup(name, arity, 0)
+    }
+
+    pub fn is_kind_of(&self, name: &str) -> bool {
+        self.module.inherits_from(name)
+    }
+}
+
+impl Debug for Object {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Object({})", self.module.name())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ast::StatementNode;
+    use crate::evaluator::environment::Environment;
+    use crate::lexer2::Lexer;
+    use crate::parser::Parser;
+    use crate::runtime::object::{Module, ModuleKind};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    fn module(
+        kind: ModuleKind,
+        name: &str,
+        parent: Option<&Rc<Module>>,
+        mixins: Vec<&Rc<Module>>,
+        methods: &str,
+    ) -> Rc<Module> {
+        let input = format!("object stub {{ {} }}", methods);
+        let lexer = Lexer::new(input.as_str());
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program().unwrap();
+
+        let methods = match &program.statements[0] {
+            StatementNode::Object(object) => object.methods.clone(),
+            other => panic!("stmt is not ObjectDeclaration. got={:?}", other),
+        };
+
+        Rc::new(Module {
+            kind,
+            name: Some(String::from(name)),
+            parent: parent.map(Rc::clone),
+            mixins: mixins.into_iter().map(Rc::clone).collect(),
+            fields: vec![],
+            methods,
+            env: Rc::new(RefCell::new(Environment::new())),
+            file: String::from("test.wlk"),
+        })
+    }
+
+    #[test]
+    fn test_linearization_and_lookup() {
+        let object = module(ModuleKind::Class, "Object", None, vec![], "method id() = 0");
+        let ave = module(
+            ModuleKind::Class,
+            "Ave",
+            Some(&object),
+            vec![],
+            "method volar() = 1 method comer(g) = g",
+        );
+        let volador = module(
+            ModuleKind::Mixin,
+            "Volador",
+            None,
+            vec![],
+            "method volar() = 2",
+        );
+        let nadador = module(
+            ModuleKind::Mixin,
+            "Nadador",
+            None,
+            vec![],
+            "method volar() = 3",
+        );
+        let pato = module(
+            ModuleKind::Class,
+            "Pato",
+            Some(&ave),
+            vec![&volador, &nadador],
+            "method nadar() = 4",
+        );
+
+        let names: Vec<String> = pato
+            .linearization()
+            .iter()
+            .map(|module| module.name())
+            .collect();
+        assert_eq!(names, vec!["Pato", "Nadador", "Volador", "Ave", "Object"]);
+
+        let (level, method) = pato.lookup("volar", 0, 0).unwrap();
+        assert_eq!((level, method.name.value.as_str()), (1, "volar"));
+
+        let (level, _) = pato.lookup("volar", 0, level + 1).unwrap();
+        assert_eq!(level, 2);
+
+        assert_eq!(pato.lookup("comer", 1, 0).unwrap().0, 3);
+        assert_eq!(pato.lookup("id", 0, 0).unwrap().0, 4);
+        assert!(pato.lookup("comer", 0, 0).is_none());
+        assert!(pato.lookup("nadar", 0, 1).is_none());
+
+        assert!(pato.inherits_from("Ave"));
+        assert!(pato.inherits_from("Volador"));
+        assert!(!ave.inherits_from("Pato"));
+    }
+
+    #[test]
+    fn test_nested_mixins() {
+        let object = module(ModuleKind::Class, "Object", None, vec![], "");
+        let ruidoso = module(ModuleKind::Mixin, "Ruidoso", None, vec![], "");
+        let volador = module(ModuleKind::Mixin, "Volador", None, vec![], "");
+        let planeador = module(
+            ModuleKind::Mixin,
+            "Planeador",
+            None,
+            vec![&volador],
+            "method volar() = 1",
+        );
+        let loro = module(
+            ModuleKind::Class,
+            "Loro",
+            Some(&object),
+            vec![&volador, &planeador, &ruidoso],
+            "",
+        );
+
+        let names: Vec<String> = loro
+            .linearization()
+            .iter()
+            .map(|module| module.name())
+            .collect();
+        assert_eq!(
+            names,
+            vec!["Loro", "Ruidoso", "Planeador", "Volador", "Object"]
+        );
+        assert!(planeador.inherits_from("Volador"));
+    }
+}
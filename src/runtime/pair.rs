@@ -0,0 +1,43 @@
+#[derive(Debug, PartialEq, Clone)]
+pub struct Pair<T> {
+    x: T,
+    y: T,
+}
+
+impl<T> Pair<T> {
+    pub fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    pub fn x(&self) -> &T {
+        &self.x
+    }
+
+    pub fn y(&self) -> &T {
+        &self.y
+    }
+
+    pub fn key(&self) -> &T {
+        &self.x
+    }
+
+    pub fn value(&self) -> &T {
+        &self.y
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::runtime::pair::Pair;
+
+    #[test]
+    fn test_accessors() {
+        let pair = Pair::new("clave", "valor");
+
+        assert_eq!(*pair.x(), "clave");
+        assert_eq!(*pair.y(), "valor");
+        assert_eq!(pair.key(), pair.x());
+        assert_eq!(pair.value(), pair.y());
+        assert_eq!(pair, Pair::new("clave", "valor"));
+    }
+}
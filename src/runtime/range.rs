@@ -0,0 +1,178 @@
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Range {
+    pub start: i64,
+    pub end: i64,
+    pub step: i64,
+}
+
+impl Range {
+    pub fn new(start: i64, end: i64) -> Self {
+        let step = if start <= end { 1 } else { -1 };
+        Self { start, end, step }
+    }
+
+    pub fn step(self, step: i64) -> Result<Range, String> {
+        if step == 0 {
+            return Err(String::from("range step cannot be zero"));
+        }
+        Ok(Self { step, ..self })
+    }
+
+    pub fn iter(&self) -> RangeIter {
+        RangeIter {
+            next: Some(self.start),
+            end: self.end,
+            step: self.step,
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.iter().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        self.iter().any(|n| n == value)
+    }
+
+    /// The sum of the elements, or None if it does not fit in an i64.
+    pub fn sum(&self) -> Option<i64> {
+        self.iter().try_fold(0i64, |total, n| total.checked_add(n))
+    }
+
+    /// Maps every element, stopping at the first error.
+    pub fn map<T, E, F: FnMut(i64) -> Result<T, E>>(&self, f: F) -> Result<Vec<T>, E> {
+        self.iter().map(f).collect()
+    }
+
+    /// Keeps the elements the predicate accepts, stopping at the first error.
+    pub fn filter<E, F: FnMut(i64) -> Result<bool, E>>(&self, mut f: F) -> Result<Vec<i64>, E> {
+        let mut kept = vec![];
+
+        for n in self {
+            if f(n)? {
+                kept.push(n);
+            }
+        }
+
+        Ok(kept)
+    }
+}
+
+impl IntoIterator for &Range {
+    type Item = i64;
+    type IntoIter = RangeIter;
+
+    fn into_iter(self) -> RangeIter {
+        self.iter()
+    }
+}
+
+pub struct RangeIter {
+    next: Option<i64>,
+    end: i64,
+    step: i64,
+}
+
+impl Iterator for RangeIter {
+    type Item = i64;
+
+    fn next(&mut self) -> Option<i64> {
+        let current = self.next?;
+        let in_range = if self.step > 0 {
+            current <= self.end
+        } else {
+            current >= self.end
+        };
+
+        if !in_range {
+            return None;
+        }
+
+        self.next = current.checked_add(self.step);
+        Some(current)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::runtime::range::Range;
+
+    #[test]
+    fn test_iteration() {
+        let tests = vec![
+            (Range::new(1, 5), vec![1, 2, 3, 4, 5]),
+            (Range::new(5, 1), vec![5, 4, 3, 2, 1]),
+            (Range::new(3, 3), vec![3]),
+            (Range::new(1, 10).step(3).unwrap(), vec![1, 4, 7, 10]),
+            (Range::new(10, 1).step(-4).unwrap(), vec![10, 6, 2]),
+            (Range::new(1, 5).step(-1).unwrap(), vec![]),
+        ];
+
+        for (range, expected) in tests {
+            let got: Vec<i64> = range.iter().collect();
+            assert_eq!(got, expected, "wrong elements for range={:?}", range);
+            assert_eq!(range.size(), expected.len());
+            assert_eq!(range.is_empty(), expected.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_iteration_near_limits() {
+        let range = Range::new(i64::MAX - 1, i64::MAX);
+        assert_eq!(
+            range.iter().collect::<Vec<i64>>(),
+            vec![i64::MAX - 1, i64::MAX]
+        );
+
+        let range = Range::new(i64::MIN + 1, i64::MIN);
+        assert_eq!(
+            range.iter().collect::<Vec<i64>>(),
+            vec![i64::MIN + 1, i64::MIN]
+        );
+    }
+
+    #[test]
+    fn test_sum_map_filter() {
+        let range = Range::new(1, 10);
+
+        assert_eq!(range.sum(), Some(55));
+        assert_eq!(Range::new(i64::MAX - 1, i64::MAX).sum(), None);
+        assert_eq!(range.map(|n| Ok::<i64, ()>(n * n)).unwrap()[..3], [1, 4, 9]);
+        assert_eq!(
+            range.filter(|n| Ok::<bool, ()>(n % 2 == 0)),
+            Ok(vec![2, 4, 6, 8, 10])
+        );
+
+        let mut visited = vec![];
+        let result = range.filter(|n| {
+            visited.push(n);
+            if n < 3 {
+                Ok(true)
+            } else {
+                Err(n)
+            }
+        });
+        assert_eq!(result, Err(3));
+        assert_eq!(visited, vec![1, 2, 3]);
+        assert!(range.contains(7));
+        assert!(!range.contains(11));
+
+        let mut visited = vec![];
+        for n in &Range::new(3, 1) {
+            visited.push(n);
+        }
+        assert_eq!(visited, vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn test_zero_step() {
+        assert_eq!(
+            Range::new(1, 10).step(0),
+            Err(String::from("range step cannot be zero"))
+        );
+    }
+}
@@ -0,0 +1,49 @@
+use crate::token::Position;
+use std::fmt::{Display, Formatter};
+
+/// One line of a Wollok stack trace: the method (or program) that was
+/// running and the position it had reached in its source file.
+#[derive(Debug, PartialEq, Clone)]
+pub struct StackFrame {
+    pub description: String,
+    pub file: String,
+    pub position: Position,
+}
+
+impl Display for StackFrame {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // Positions are 1-based, so line 0 means nothing was evaluated yet.
+        if self.position.line == 0 {
+            return write!(f, "{} [{}]", self.description, self.file);
+        }
+        write!(f, "{} [{}:{}]", self.description, self.file, self.position)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::runtime::trace::StackFrame;
+    use crate::token::Position;
+
+    #[test]
+    fn test_display() {
+        let frame = StackFrame {
+            description: String::from("pepita.volar(km)"),
+            file: String::from("aves.wlk"),
+            position: Position {
+                line: 3,
+                column: 14,
+            },
+        };
+
+        assert_eq!(frame.to_string(), "pepita.volar(km) [aves.wlk:3:14]");
+
+        let frame = StackFrame {
+            description: String::from("top level"),
+            file: String::from("aves.wlk"),
+            position: Position::default(),
+        };
+
+        assert_eq!(frame.to_string(), "top level [aves.wlk]");
+    }
+}
@@ -0,0 +1,240 @@
+use crate::ast::{ClosureLiteral, Node};
+use crate::evaluator::environment::Environment;
+use crate::runtime::object::{Module, ModuleKind, Object};
+use crate::runtime::pair::Pair;
+use crate::runtime::range::Range;
+use std::cell::RefCell;
+use std::fmt::{Debug, Display, Formatter};
+use std::rc::Rc;
+
+#[derive(Debug, Clone)]
+pub enum Value {
+    Null,
+    Boolean(bool),
+    Number(f64),
+    String(String),
+    List(Rc<RefCell<Vec<Value>>>),
+    Set(Rc<RefCell<Vec<Value>>>),
+    Dictionary(Rc<RefCell<Vec<(Value, Value)>>>),
+    Range(Range),
+    Pair(Rc<Pair<Value>>),
+    Object(Rc<Object>),
+    Module(Rc<Module>),
+    Closure(Rc<Closure>),
+}
+
+pub struct Closure {
+    pub literal: ClosureLiteral,
+    pub env: Rc<RefCell<Environment>>,
+}
+
+impl Debug for Closure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Closure({})", self.literal.print_string())
+    }
+}
+
+impl Value {
+    pub fn type_name(&self) -> String {
+        match self {
+            Value::Null => String::from("Null"),
+            Value::Boolean(_) => String::from("Boolean"),
+            Value::Number(_) => String::from("Number"),
+            Value::String(_) => String::from("String"),
+            Value::List(_) => String::from("List"),
+            Value::Set(_) => String::from("Set"),
+            Value::Dictionary(_) => String::from("Dictionary"),
+            Value::Range(_) => String::from("Range"),
+            Value::Pair(_) => String::from("Pair"),
+            Value::Object(object) => object.module.name(),
+            Value::Module(_) => String::from("Module"),
+            Value::Closure(_) => String::from("Closure"),
+        }
+    }
+
+    pub fn to_wollok_string(&self) -> String {
+        match self {
+            Value::String(string) => string.clone(),
+            other => other.to_string(),
+        }
+    }
+}
+
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Value::Null, Value::Null) => true,
+            (Value::Boolean(left), Value::Boolean(right)) => left == right,
+            (Value::Number(left), Value::Number(right)) => left == right,
+            (Value::String(left), Value::String(right)) => left == right,
+            // A collection may contain itself, so identity is checked first.
+            (Value::List(left), Value::List(right)) => {
+                Rc::ptr_eq(left, right) || *left.borrow() == *right.borrow()
+            }
+            (Value::Set(left), Value::Set(right)) if Rc::ptr_eq(left, right) => true,
+            (Value::Set(left), Value::Set(right)) => {
+                let left = left.borrow();
+                let right = right.borrow();
+                left.len() == right.len() && left.iter().all(|value| right.contains(value))
+            }
+            (Value::Dictionary(left), Value::Dictionary(right)) if Rc::ptr_eq(left, right) => true,
+            (Value::Dictionary(left), Value::Dictionary(right)) => {
+                let left = left.borrow();
+                let right = right.borrow();
+                left.len() == right.len() && left.iter().all(|entry| right.contains(entry))
+            }
+            (Value::Range(left), Value::Range(right)) => left == right,
+            (Value::Pair(left), Value::Pair(right)) => left == right,
+            (Value::Object(left), Value::Object(right)) => Rc::ptr_eq(left, right),
+            (Value::Module(left), Value::Module(right)) => Rc::ptr_eq(left, right),
+            (Value::Closure(left), Value::Closure(right)) => Rc::ptr_eq(left, right),
+            _ => false,
+        }
+    }
+}
+
+/// How deep Display goes into nested collections. A collection may contain
+/// itself, and this keeps printing it from running out of stack.
+const MAX_DISPLAY_DEPTH: usize = 16;
+
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display(0))
+    }
+}
+
+impl Value {
+    fn display(&self, depth: usize) -> String {
+        let nested = matches!(
+            self,
+            Value::List(_) | Value::Set(_) | Value::Dictionary(_) | Value::Pair(_)
+        );
+        if nested && depth >= MAX_DISPLAY_DEPTH {
+            return String::from("...");
+        }
+
+        match self {
+            Value::Null => String::from("null"),
+            Value::Boolean(value) => value.to_string(),
+            Value::Number(value) => format_number(*value),
+            Value::String(value) => format!("{:?}", value),
+            Value::List(elements) => format!("[{}]", join(&elements.borrow(), depth + 1)),
+            Value::Set(elements) => format!("#{{{}}}", join(&elements.borrow(), depth + 1)),
+            Value::Dictionary(entries) => {
+                let entries: Vec<String> = entries
+                    .borrow()
+                    .iter()
+                    .map(|(key, value)| {
+                        format!("{} -> {}", key.display(depth + 1), value.display(depth + 1))
+                    })
+                    .collect();
+                format!("a Dictionary [{}]", entries.join(", "))
+            }
+            Value::Range(range) => format!("{}..{}", range.start, range.end),
+            Value::Pair(pair) => format!(
+                "{} -> {}",
+                pair.x().display(depth + 1),
+                pair.y().display(depth + 1)
+            ),
+            Value::Object(object) => match (object.module.kind, &object.module.name) {
+                (ModuleKind::Class, Some(name)) => format!("{} {}", article(name), name),
+                (_, Some(name)) => name.clone(),
+                (_, None) => String::from("an Object"),
+            },
+            Value::Module(module) => module.name(),
+            Value::Closure(closure) => closure.literal.print_string(),
+        }
+    }
+}
+
+pub fn format_number(value: f64) -> String {
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        format!("{}", value as i64)
+    } else {
+        format!("{}", value)
+    }
+}
+
+fn article(name: &str) -> &'static str {
+    match name.chars().next() {
+        Some(c) if "AEIOUaeiou".contains(c) => "an",
+        _ => "a",
+    }
+}
+
+fn join(values: &[Value], depth: usize) -> String {
+    values
+        .iter()
+        .map(|value| value.display(depth))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod test {
+    use crate::runtime::pair::Pair;
+    use crate::runtime::range::Range;
+    use crate::runtime::value::Value;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_print_string() {
+        let tests = vec![
+            (Value::Null, "null"),
+            (Value::Boolean(true), "true"),
+            (Value::Number(5.0), "5"),
+            (Value::Number(-2.5), "-2.5"),
+            (Value::String(String::from("hola")), "\"hola\""),
+            (
+                Value::List(Rc::new(RefCell::new(vec![
+                    Value::Number(1.0),
+                    Value::String(String::from("a")),
+                ]))),
+                "[1, \"a\"]",
+            ),
+            (
+                Value::Set(Rc::new(RefCell::new(vec![Value::Number(1.0)]))),
+                "#{1}",
+            ),
+            (Value::Range(Range::new(1, 5)), "1..5"),
+            (
+                Value::Pair(Rc::new(Pair::new(
+                    Value::String(String::from("clave")),
+                    Value::Number(2.0),
+                ))),
+                "\"clave\" -> 2",
+            ),
+        ];
+
+        for (value, expected) in tests {
+            assert_eq!(value.to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn test_print_self_containing() {
+        let list = Rc::new(RefCell::new(vec![]));
+        list.borrow_mut().push(Value::List(Rc::clone(&list)));
+
+        let printed = Value::List(Rc::clone(&list)).to_string();
+        assert!(printed.starts_with("[[[") && printed.contains("..."));
+        list.borrow_mut().clear();
+    }
+
+    #[test]
+    fn test_equality() {
+        let set = |values: Vec<Value>| Value::Set(Rc::new(RefCell::new(values)));
+
+        assert_eq!(Value::Number(1.0), Value::Number(1.0));
+        assert_ne!(Value::Number(1.0), Value::String(String::from("1")));
+        assert_eq!(
+            set(vec![Value::Number(1.0), Value::Number(2.0)]),
+            set(vec![Value::Number(2.0), Value::Number(1.0)])
+        );
+        assert_ne!(
+            set(vec![Value::Number(1.0)]),
+            set(vec![Value::Number(1.0), Value::Number(2.0)])
+        );
+    }
+}
@@ -1,12 +1,46 @@
+#[cfg(feature = "json")]
+use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
 
 #[derive(Debug, PartialEq, Clone, Default)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
 pub struct Token {
     pub kind: TokenKind,
     pub literal: String,
+    pub span: Span,
+}
+
+/// A 1-based line and column in the source text.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// The source range of a token, from its first character up to (not
+/// including) the position right after its last one.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct Span {
+    pub start: Position,
+    pub end: Position,
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+impl Display for Span {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
 }
 
 #[derive(Debug, PartialEq, Clone, Copy, Default, Hash, Eq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
 pub enum TokenKind {
     EOF,
     #[default]
@@ -39,7 +73,7 @@ pub enum TokenKind {
 
     Bang,
     Asterisk,
-    Slash,
+    Hash,
 
     GreaterThan,
     LessThan,
@@ -51,6 +85,41 @@ pub enum TokenKind {
     Return,
     Eq,
     NotEq,
+
+    Dot,
+    Object,
+    Method,
+    Override,
+    Self_,
+    Super,
+    Inherits,
+    Throw,
+    Try,
+    Catch,
+    Then,
+    Always,
+    Package,
+    Import,
+    String,
+    Program,
+    Describe,
+    Test,
+    Only,
+    DotDot,
+    Arrow,
+    FatArrow,
+    GreaterEq,
+    LessEq,
+    Modulo,
+    And,
+    Or,
+    Not,
+    Null,
+    Class,
+    Mixin,
+    Mixed,
+    With,
+    New,
 }
 
 impl Display for TokenKind {
@@ -73,11 +142,13 @@ impl Display for TokenKind {
             TokenKind::RightBracket => write!(f, "]"),
             TokenKind::LeftBrace => write!(f, "{{"),
             TokenKind::RightBrace => write!(f, "}}"),
+            TokenKind::Number => write!(f, "number"),
+            TokenKind::Identifier => write!(f, "identifier"),
             TokenKind::Function => write!(f, "function"),
             TokenKind::Var => write!(f, "var"),
             TokenKind::Const => write!(f, "const"),
             TokenKind::Bang => write!(f, "!"),
-            TokenKind::Slash => write!(f, "#"),
+            TokenKind::Hash => write!(f, "#"),
             TokenKind::GreaterThan => write!(f, ">"),
             TokenKind::LessThan => write!(f, "<"),
             TokenKind::True => write!(f, "true"),
@@ -87,20 +158,85 @@ impl Display for TokenKind {
             TokenKind::Return => write!(f, "return"),
             TokenKind::Eq => write!(f, "=="),
             TokenKind::NotEq => write!(f, "!="),
+            TokenKind::Dot => write!(f, "."),
+            TokenKind::Object => write!(f, "object"),
+            TokenKind::Method => write!(f, "method"),
+            TokenKind::Override => write!(f, "override"),
+            TokenKind::Self_ => write!(f, "self"),
+            TokenKind::Super => write!(f, "super"),
+            TokenKind::Inherits => write!(f, "inherits"),
+            TokenKind::Throw => write!(f, "throw"),
+            TokenKind::Try => write!(f, "try"),
+            TokenKind::Catch => write!(f, "catch"),
+            TokenKind::Then => write!(f, "then"),
+            TokenKind::Always => write!(f, "always"),
+            TokenKind::Package => write!(f, "package"),
+            TokenKind::Import => write!(f, "import"),
+            TokenKind::String => write!(f, "string"),
+            TokenKind::Program => write!(f, "program"),
+            TokenKind::Describe => write!(f, "describe"),
+            TokenKind::Test => write!(f, "test"),
+            TokenKind::Only => write!(f, "only"),
+            TokenKind::DotDot => write!(f, ".."),
+            TokenKind::Arrow => write!(f, "->"),
+            TokenKind::FatArrow => write!(f, "=>"),
+            TokenKind::GreaterEq => write!(f, ">="),
+            TokenKind::LessEq => write!(f, "<="),
+            TokenKind::Modulo => write!(f, "%"),
+            TokenKind::And => write!(f, "&&"),
+            TokenKind::Or => write!(f, "||"),
+            TokenKind::Not => write!(f, "not"),
+            TokenKind::Null => write!(f, "null"),
+            TokenKind::Class => write!(f, "class"),
+            TokenKind::Mixin => write!(f, "mixin"),
+            TokenKind::Mixed => write!(f, "mixed"),
+            TokenKind::With => write!(f, "with"),
+            TokenKind::New => write!(f, "new"),
             _ => write!(f, "other"),
         }
     }
 }
 
+/// The reserved words of the language and the token kind of each.
+pub const KEYWORDS: [(&str, TokenKind); 33] = [
+    ("var", TokenKind::Var),
+    ("const", TokenKind::Const),
+    ("true", TokenKind::True),
+    ("false", TokenKind::False),
+    ("if", TokenKind::If),
+    ("else", TokenKind::Else),
+    ("return", TokenKind::Return),
+    ("object", TokenKind::Object),
+    ("method", TokenKind::Method),
+    ("override", TokenKind::Override),
+    ("self", TokenKind::Self_),
+    ("super", TokenKind::Super),
+    ("inherits", TokenKind::Inherits),
+    ("throw", TokenKind::Throw),
+    ("try", TokenKind::Try),
+    ("catch", TokenKind::Catch),
+    ("then", TokenKind::Then),
+    ("always", TokenKind::Always),
+    ("package", TokenKind::Package),
+    ("import", TokenKind::Import),
+    ("program", TokenKind::Program),
+    ("describe", TokenKind::Describe),
+    ("test", TokenKind::Test),
+    ("only", TokenKind::Only),
+    ("and", TokenKind::And),
+    ("or", TokenKind::Or),
+    ("not", TokenKind::Not),
+    ("null", TokenKind::Null),
+    ("class", TokenKind::Class),
+    ("mixin", TokenKind::Mixin),
+    ("mixed", TokenKind::Mixed),
+    ("with", TokenKind::With),
+    ("new", TokenKind::New),
+];
+
 pub fn lookup_ident(identifier: &str) -> TokenKind {
-    match identifier {
-        "var" => TokenKind::Var,
-        "const" => TokenKind::Const,
-        "true" => TokenKind::True,
-        "false" => TokenKind::False,
-        "if" => TokenKind::If,
-        "else" => TokenKind::Else,
-        "return" => TokenKind::Return,
-        _ => TokenKind::Identifier,
-    }
+    KEYWORDS
+        .iter()
+        .find(|(keyword, _)| *keyword == identifier)
+        .map_or(TokenKind::Identifier, |(_, kind)| *kind)
 }
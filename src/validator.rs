@@ -0,0 +1,402 @@
+use crate::ast::{
+    BlockStatement, DescribeBlock, ExpressionNode, MethodBody, MethodDeclaration, Node, Program,
+    StatementNode, VarStatement,
+};
+use std::collections::HashSet;
+
+#[derive(PartialEq, Clone, Copy)]
+enum Scope {
+    TopLevel,
+    Object,
+    Method { overrides: bool },
+}
+
+struct Validator {
+    errors: Vec<String>,
+}
+
+impl Validator {
+    fn new() -> Self {
+        Self { errors: vec![] }
+    }
+
+    fn validate(mut self, program: &Program) -> Vec<String> {
+        for stmt in program.statements.as_slice() {
+            self.validate_statement(stmt, Scope::TopLevel);
+        }
+        self.errors
+    }
+
+    fn validate_statement(&mut self, stmt: &StatementNode, scope: Scope) {
+        match stmt {
+            StatementNode::Var(var_stmt) => {
+                if let Some(value) = &var_stmt.value {
+                    self.validate_expression(value, scope);
+                }
+            }
+            StatementNode::Assign(assign) => self.validate_expression(&assign.value, scope),
+            StatementNode::Return(ret_stmt) => {
+                if let Some(ret_value) = &ret_stmt.ret_value {
+                    self.validate_expression(ret_value, scope);
+                }
+            }
+            StatementNode::Expression(exp_stmt) => {
+                if let Some(expression) = &exp_stmt.expression {
+                    self.validate_expression(expression, scope);
+                }
+            }
+            StatementNode::Throw(throw) => self.validate_expression(&throw.exception, scope),
+            StatementNode::Program(program) => self.validate_block(&program.body, scope),
+            StatementNode::Describe(describe) => self.validate_describe(describe),
+            StatementNode::Test(test) => self.validate_block(&test.body, scope),
+            StatementNode::Package(package) => {
+                for stmt in package.statements.as_slice() {
+                    self.validate_statement(stmt, scope);
+                }
+            }
+            StatementNode::Object(object) => self.validate_object(&object.fields, &object.methods),
+            StatementNode::Class(class) => self.validate_object(&class.fields, &class.methods),
+            StatementNode::Mixin(mixin) => self.validate_object(&mixin.fields, &mixin.methods),
+        }
+    }
+
+    fn validate_object(&mut self, fields: &[VarStatement], methods: &[MethodDeclaration]) {
+        for field in fields {
+            if let Some(value) = &field.value {
+                self.validate_expression(value, Scope::Object);
+            }
+        }
+        for method in methods {
+            self.validate_method(method);
+        }
+    }
+
+    fn validate_describe(&mut self, describe: &DescribeBlock) {
+        self.validate_object(&describe.fields, &describe.methods);
+
+        let mut names = HashSet::new();
+        for test in describe.tests.as_slice() {
+            if !names.insert(test.name.value.as_str()) {
+                self.errors.push(format!(
+                    "duplicate test name {} in describe {}",
+                    test.name.print_string(),
+                    describe.name.print_string()
+                ));
+            }
+            self.validate_block(&test.body, Scope::Method { overrides: false });
+        }
+    }
+
+    fn validate_method(&mut self, method: &MethodDeclaration) {
+        let scope = Scope::Method {
+            overrides: method.overrides,
+        };
+
+        match &method.body {
+            MethodBody::Abstract => {}
+            MethodBody::Expression(expression) => self.validate_expression(expression, scope),
+            MethodBody::Block(block) => self.validate_block(block, scope),
+        }
+    }
+
+    fn validate_block(&mut self, block: &BlockStatement, scope: Scope) {
+        for stmt in block.statements.as_slice() {
+            self.validate_statement(stmt, scope);
+        }
+    }
+
+    fn validate_expression(&mut self, expression: &ExpressionNode, scope: Scope) {
+        match expression {
+            ExpressionNode::IdentifierNode(_)
+            | ExpressionNode::Integer(_)
+            | ExpressionNode::Decimal(_)
+            | ExpressionNode::String(_)
+            | ExpressionNode::Boolean(_)
+            | ExpressionNode::Null(_) => {}
+            ExpressionNode::Prefix(prefix) => self.validate_expression(&prefix.right, scope),
+            ExpressionNode::Infix(infix) => {
+                self.validate_expression(&infix.left, scope);
+                self.validate_expression(&infix.right, scope);
+            }
+            ExpressionNode::If(if_exp) => {
+                self.validate_expression(&if_exp.condition, scope);
+                self.validate_block(&if_exp.consequence, scope);
+                if let Some(alternative) = &if_exp.alternative {
+                    self.validate_block(alternative, scope);
+                }
+            }
+            ExpressionNode::Closure(closure) => self.validate_block(&closure.body, scope),
+            ExpressionNode::Range(range) => {
+                self.validate_expression(&range.start, scope);
+                self.validate_expression(&range.end, scope);
+            }
+            ExpressionNode::SelfNode(_) => {
+                if scope == Scope::TopLevel {
+                    self.errors
+                        .push(String::from("self cannot be used at program top level"));
+                }
+            }
+            ExpressionNode::Super(super_call) => {
+                if scope != (Scope::Method { overrides: true }) {
+                    self.errors.push(String::from(
+                        "super can only be used inside a method that overrides another one",
+                    ));
+                }
+                for argument in super_call.arguments.as_slice() {
+                    self.validate_expression(argument, scope);
+                }
+            }
+            ExpressionNode::Send(send) => {
+                self.validate_expression(&send.receiver, scope);
+                for argument in send.arguments.as_slice() {
+                    self.validate_expression(argument, scope);
+                }
+            }
+            ExpressionNode::List(list) => {
+                for element in list.elements.as_slice() {
+                    self.validate_expression(element, scope);
+                }
+            }
+            ExpressionNode::Set(set) => {
+                for element in set.elements.as_slice() {
+                    self.validate_expression(element, scope);
+                }
+            }
+            ExpressionNode::Try(try_exp) => {
+                if try_exp.catches.is_empty() && try_exp.always.is_none() {
+                    self.errors.push(String::from(
+                        "try must have at least one catch or then always block",
+                    ));
+                }
+                self.validate_block(&try_exp.body, scope);
+                for catch in try_exp.catches.as_slice() {
+                    self.validate_block(&catch.body, scope);
+                }
+                if let Some(always) = &try_exp.always {
+                    self.validate_block(always, scope);
+                }
+            }
+            ExpressionNode::ObjectLiteral(object) => {
+                self.validate_object(&object.fields, &object.methods)
+            }
+            ExpressionNode::New(new) => {
+                let mut names = HashSet::new();
+                for argument in new.arguments.as_slice() {
+                    if !names.insert(argument.name.value.as_str()) {
+                        self.errors.push(format!(
+                            "duplicate argument {} in new {}",
+                            argument.name.value,
+                            new.class.print_string()
+                        ));
+                    }
+                    self.validate_expression(&argument.value, scope);
+                }
+            }
+        }
+    }
+}
+
+pub fn validate(program: &Program) -> Vec<String> {
+    Validator::new().validate(program)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::lexer2::Lexer;
+    use crate::parser::Parser;
+    use crate::validator::validate;
+
+    #[test]
+    fn test_valid_self_and_super() {
+        let input = r#"
+            object pepita {
+                var energia = self.inicial()
+
+                method inicial() = 100
+                override method volar(km) {
+                    super(km)
+                    return self.energia()
+                }
+            }
+        "#;
+
+        let errors = validate_input(input);
+
+        assert!(errors.is_empty(), "unexpected errors: {:?}", errors);
+    }
+
+    #[test]
+    fn test_super_in_mixin() {
+        let tests = vec![
+            "mixin Ruidoso { override method hablar() = super() + \"!\" }",
+            "mixin Griton mixed with Ruidoso { override method hablar() = super() }",
+        ];
+
+        for input in tests {
+            let errors = validate_input(input);
+
+            assert!(
+                errors.is_empty(),
+                "unexpected errors for input={}: {:?}",
+                input,
+                errors
+            );
+        }
+
+        assert_eq!(
+            validate_input("mixin Ruidoso { method hablar() = super() }"),
+            vec![String::from(
+                "super can only be used inside a method that overrides another one"
+            )]
+        );
+    }
+
+    #[test]
+    fn test_super_outside_overriding_method() {
+        let tests = vec![
+            "object pepita { method volar(km) { super(km) } }",
+            "object pepita { var energia = super() }",
+            "super(1);",
+        ];
+
+        for input in tests {
+            let errors = validate_input(input);
+
+            assert_eq!(
+                errors,
+                vec![String::from(
+                    "super can only be used inside a method that overrides another one"
+                )],
+                "wrong errors for input={}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_self_at_top_level() {
+        let tests = vec![
+            "self;",
+            "var x = self.energia();",
+            "return 1 + self;",
+            "program saludo { self.hola() }",
+        ];
+
+        for input in tests {
+            let errors = validate_input(input);
+
+            assert_eq!(
+                errors,
+                vec![String::from("self cannot be used at program top level")],
+                "wrong errors for input={}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_object_literal_members() {
+        let input = r#"
+            const stub = object { method doble() = self.valor() * 2 }
+            object inherits Ave { method volar(km) = super(km) }
+        "#;
+
+        let errors = validate_input(input);
+
+        assert_eq!(
+            errors,
+            vec![String::from(
+                "super can only be used inside a method that overrides another one"
+            )]
+        );
+    }
+
+    #[test]
+    fn test_try_without_handlers() {
+        let tests = vec![
+            ("try { 1 } catch e { 2 }", vec![]),
+            ("try { 1 } then always { 2 }", vec![]),
+            (
+                "try { 1 }",
+                vec![String::from(
+                    "try must have at least one catch or then always block",
+                )],
+            ),
+            (
+                "try { try { self } }",
+                vec![
+                    String::from("try must have at least one catch or then always block"),
+                    String::from("try must have at least one catch or then always block"),
+                    String::from("self cannot be used at program top level"),
+                ],
+            ),
+        ];
+
+        for (input, expected) in tests {
+            let errors = validate_input(input);
+
+            assert_eq!(errors, expected, "wrong errors for input={}", input);
+        }
+    }
+
+    #[test]
+    fn test_duplicate_test_names() {
+        let input = r#"
+            describe "pepita" {
+                test "vuela" { }
+                test "come" { }
+                only test "vuela" { }
+            }
+            describe "otro" {
+                test "vuela" { self.hola() }
+            }
+            test "vuela" { }
+        "#;
+
+        let errors = validate_input(input);
+
+        assert_eq!(
+            errors,
+            vec![String::from(
+                r#"duplicate test name "vuela" in describe "pepita""#
+            )]
+        );
+    }
+
+    #[test]
+    fn test_classes_and_new() {
+        let input = r#"
+            mixin Volador { method volar() = self.energia() }
+            class Ave mixed with Volador {
+                var energia = 0
+                method comer() = super()
+            }
+            const pepita = new Ave(energia = 10, energia = self)
+        "#;
+
+        let errors = validate_input(input);
+
+        assert_eq!(
+            errors,
+            vec![
+                String::from("super can only be used inside a method that overrides another one"),
+                String::from("duplicate argument energia in new Ave"),
+                String::from("self cannot be used at program top level"),
+            ]
+        );
+    }
+
+    fn validate_input(input: &str) -> Vec<String> {
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program().unwrap();
+
+        assert!(
+            parser.errors().is_empty(),
+            "parser errors: {:?}",
+            parser.errors()
+        );
+
+        validate(&program)
+    }
+}
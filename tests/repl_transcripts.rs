@@ -0,0 +1,132 @@
+//! Drives the REPL with the sessions in tests/transcripts and compares what
+//! it prints with what the transcript says.
+//!
+//! A transcript is what a user would see on the terminal: lines starting
+//! with the prompt (`>> `) or the continuation prompt (`.. `) are typed in,
+//! every other line is output. End of input is implied after the last line.
+
+use rollok::repl::start;
+use std::fs;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+
+const PROMPTS: [&str; 2] = [">> ", ".. "];
+
+/// Splits a transcript into the input to type and the output to expect.
+fn script(transcript: &str) -> (String, String) {
+    let mut input = String::new();
+    let mut expected = String::new();
+
+    for line in transcript.lines() {
+        // Editors strip the trailing space of a prompt with nothing typed.
+        let prompt = PROMPTS
+            .iter()
+            .find(|prompt| line.starts_with(*prompt) || line == prompt.trim_end());
+        match prompt {
+            Some(prompt) => {
+                expected.push_str(prompt);
+                input.push_str(line.get(prompt.len()..).unwrap_or(""));
+                input.push('\n');
+            }
+            None => {
+                expected.push_str(line);
+                expected.push('\n');
+            }
+        }
+    }
+
+    // The REPL shows a last prompt, finds no more input and ends the line.
+    expected.push_str(">> \n");
+    (input, expected)
+}
+
+/// Joins what the REPL printed with the input it was given, so a failure
+/// reads like the transcript it should have matched.
+fn render(input: &str, output: &str) -> String {
+    let mut lines = input.lines();
+    let mut rendered = String::new();
+    let mut rest = output;
+
+    while !rest.is_empty() {
+        match PROMPTS.iter().find(|prompt| rest.starts_with(*prompt)) {
+            Some(prompt) => {
+                rendered.push_str(prompt);
+                rendered.push_str(lines.next().unwrap_or(""));
+                rendered.push('\n');
+                rest = &rest[prompt.len()..];
+                if rest.starts_with('\n') {
+                    rest = &rest[1..];
+                }
+            }
+            None => {
+                let end = rest.find('\n').map_or(rest.len(), |end| end + 1);
+                rendered.push_str(&rest[..end]);
+                rest = &rest[end..];
+            }
+        }
+    }
+
+    rendered
+}
+
+fn check(path: &Path) {
+    let transcript = fs::read_to_string(path).unwrap();
+    let (input, expected) = script(&transcript);
+
+    let mut output = vec![];
+    start(input.as_bytes(), &mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    assert!(
+        output == expected,
+        "transcript {} does not match, the session was:\n{}",
+        path.display(),
+        render(&input, &output)
+    );
+}
+
+#[test]
+fn test_transcripts() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("transcripts");
+    let mut paths: Vec<_> = fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    paths.sort();
+
+    assert!(!paths.is_empty(), "no transcripts found");
+    for path in paths {
+        check(&path);
+    }
+}
+
+#[test]
+fn test_empty_input() {
+    let mut output = vec![];
+    start("".as_bytes(), &mut output).unwrap();
+
+    assert_eq!(String::from_utf8(output).unwrap(), ">> \n");
+}
+
+/// Output that refuses every write, like a pipe whose reader has exited.
+struct ClosedPipe;
+
+impl Write for ClosedPipe {
+    fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+        Err(io::Error::from(io::ErrorKind::BrokenPipe))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_closed_output() {
+    let err = start("1 + 1\n".as_bytes(), ClosedPipe).unwrap_err();
+
+    assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+}